@@ -0,0 +1,137 @@
+//! Genera `assets/systems/asteroid_belt.toml`
+//!
+//! Uso: `cargo run --example generate_asteroid_belt [-- ARCHIVO]`
+//!
+//! Los asteroides salen de un generador pseudoaleatorio con semilla fija, así
+//! que el archivo es reproducible: para cambiar el cinturón se editan las
+//! constantes de abajo y se vuelve a generar.
+
+use std::fmt::Write as _;
+use std::path::PathBuf;
+
+const SEED: u64 = 1508;
+const ASTEROIDS: usize = 300;
+const BELT_RADIUS: (f64, f64) = (12.5, 17.5); // Radio orbital mínimo y máximo
+const ASTEROID_RADIUS: (f64, f64) = (0.06, 0.18);
+
+/// Estrella y planetas fijos antes del cinturón
+const HEADER: &str = r#"# Sistema con un cinturón de asteroides (cientos de cuerpos)
+#
+# Con 64 cuerpos o más, las órbitas se actualizan en paralelo
+# (threading::PlanetThreadPool). Generado por examples/generate_asteroid_belt.rs
+# con una semilla fija; no editar a mano, regenerar con
+# `cargo run --example generate_asteroid_belt`.
+name = "Cinturón de asteroides"
+
+[camera]
+target = "Solar heart"
+distance = 40.0
+
+[[body]]
+name = "Solar heart"
+radius = 2.5
+mass = 200.0
+color = [1.0, 0.9, 0.3]
+shader = "solar_heart"
+emissive = true
+rotation_speed = 0.1
+detail = 24
+
+[[body]]
+name = "Rocky"
+radius = 0.8
+mass = 0.02
+color = [0.2, 0.5, 0.8]
+shader = "rocky"
+rotation_speed = 1.0
+orbit_radius = 8.0
+orbit_speed = 0.5
+detail = 16
+
+[[body]]
+name = "Gas Giant"
+radius = 1.5
+mass = 4.0
+color = [0.8, 0.7, 0.5]
+shader = "gas_giant"
+rotation_speed = 2.0
+orbit_radius = 24.0
+orbit_speed = 0.12
+detail = 16
+"#;
+
+/// Generador SplitMix64: pequeño, sin dependencias y estable entre plataformas
+struct Rng(u64);
+
+impl Rng {
+    fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+
+    /// Número uniforme en [min, max)
+    fn uniform(&mut self, min: f64, max: f64) -> f64 {
+        let unit = (self.next_u64() >> 11) as f64 / (1u64 << 53) as f64;
+        min + (max - min) * unit
+    }
+}
+
+fn main() {
+    let path = std::env::args()
+        .nth(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from("assets/systems/asteroid_belt.toml"));
+
+    let mut rng = Rng(SEED);
+    let mut text = String::from(HEADER);
+    for i in 0..ASTEROIDS {
+        let orbit_radius = rng.uniform(BELT_RADIUS.0, BELT_RADIUS.1);
+        let gray = rng.uniform(0.35, 0.55);
+        let radius = rng.uniform(ASTEROID_RADIUS.0, ASTEROID_RADIUS.1);
+        let rotation_speed = rng.uniform(-3.0, 3.0);
+        // Tercera ley de Kepler con un poco de dispersión
+        let orbit_speed = 2.6 / orbit_radius.powf(1.5) * rng.uniform(0.97, 1.03);
+        let eccentricity = rng.uniform(0.0, 0.08);
+        let inclination = rng.uniform(-0.06, 0.06);
+        let orbit_angle = rng.uniform(0.0, std::f64::consts::TAU);
+
+        // Sin órbita visible: tampoco llevan nombre en pantalla
+        let _ = write!(
+            text,
+            "\n[[body]]\n\
+             name = \"Asteroide {}\"\n\
+             radius = {:.3}\n\
+             mass = 0.0001\n\
+             color = [{:.2}, {:.2}, {:.2}]\n\
+             shader = \"rocky\"\n\
+             rotation_speed = {:.2}\n\
+             orbit_radius = {:.2}\n\
+             orbit_speed = {:.4}\n\
+             eccentricity = {:.3}\n\
+             inclination = {:.3}\n\
+             orbit_angle = {:.3}\n\
+             show_orbit = false\n\
+             detail = 4\n",
+            i + 1,
+            radius,
+            gray,
+            gray * 0.9,
+            gray * 0.8,
+            rotation_speed,
+            orbit_radius,
+            orbit_speed,
+            eccentricity,
+            inclination,
+            orbit_angle,
+        );
+    }
+
+    if let Err(err) = std::fs::write(&path, text) {
+        eprintln!("No se pudo escribir {}: {}", path.display(), err);
+        std::process::exit(1);
+    }
+    println!("{} asteroides guardados en {}", ASTEROIDS, path.display());
+}
@@ -0,0 +1,23 @@
+//! Genera `assets/keybindings.toml` con los atajos por defecto
+//!
+//! Uso: `cargo run --example generate_keybindings [-- ARCHIVO]`
+//!
+//! El archivo sale de `KeyBindings::default()`, así que al agregar una acción
+//! o cambiar una tecla se edita el código y se vuelve a generar.
+
+use std::path::PathBuf;
+
+use solar2::input::KeyBindings;
+
+fn main() {
+    let path = std::env::args()
+        .nth(1)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| PathBuf::from(KeyBindings::DEFAULT_PATH));
+
+    if let Err(err) = std::fs::write(&path, KeyBindings::default().to_config_string()) {
+        eprintln!("No se pudo escribir {}: {}", path.display(), err);
+        std::process::exit(1);
+    }
+    println!("Atajos por defecto guardados en {}", path.display());
+}
@@ -1,4 +1,21 @@
-use crate::math::{Vec3, Mat4};
+use crate::math::{Vec3, Vec4, Mat4, Quat};
+use super::collision::CollisionSettings;
+use super::free_flight::{CameraMode, FlightInput, FlightSettings};
+use super::warp::{Warp, WarpEffect};
+
+/// Estado guardable de la cámara (ver `Camera::snapshot`)
+#[derive(Debug, Clone, Copy)]
+pub struct CameraSnapshot {
+    pub position: Vec3,
+    pub target: Vec3,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub distance_from_target: f32,
+    pub fov: f32,
+    pub mode: CameraMode,
+    pub orientation: Quat,
+    pub velocity: Vec3,
+}
 
 pub struct Camera {
     pub position: Vec3,
@@ -11,10 +28,32 @@ pub struct Camera {
     
     // Ángulo de rotación alrededor del eje Y (para movimiento en plano eclíptico)
     pub yaw: f32,
+    // Elevación sobre el plano eclíptico (radianes, positiva = desde arriba)
+    pub pitch: f32,
     pub distance_from_target: f32,
+    pub target_radius: f32,    // Radio del cuerpo que se sigue (limita el zoom)
+
+    // Colisión con los cuerpos
+    pub collision: CollisionSettings,
+    collision_offset: Vec3,    // Corrección suave acumulada (modo órbita)
+
+    // Vuelo libre
+    pub mode: CameraMode,
+    pub orientation: Quat,     // Orientación de la nave (mira hacia -Z local)
+    pub velocity: Vec3,        // Velocidad de la nave (inercia)
+    pub flight: FlightSettings,
+
+    // Viaje animado entre objetivos
+    pub warp: Option<Warp>,
+    pub warp_duration: f32,    // Segundos por viaje (0 = cambio instantáneo)
 }
 
 impl Camera {
+    /// Elevación inicial en modo órbita
+    pub const DEFAULT_PITCH: f32 = 0.15;
+    /// Elevación máxima (evita mirar justo hacia arriba o abajo)
+    pub const MAX_PITCH: f32 = 1.45;
+
     pub fn new(aspect: f32) -> Self {
         Self {
             position: Vec3::new(0.0, 5.0, 20.0),
@@ -25,7 +64,17 @@ impl Camera {
             near: 0.1,
             far: 1000.0,
             yaw: 0.0,
+            pitch: Self::DEFAULT_PITCH,
             distance_from_target: 20.0,
+            target_radius: 0.0,
+            collision: CollisionSettings::default(),
+            collision_offset: Vec3::zero(),
+            mode: CameraMode::Orbit,
+            orientation: Quat::identity(),
+            velocity: Vec3::zero(),
+            flight: FlightSettings::default(),
+            warp: None,
+            warp_duration: 1.5,
         }
     }
 
@@ -66,34 +115,307 @@ impl Camera {
 
     /// Rota la cámara (cambia hacia dónde mira)
     pub fn rotate(&mut self, delta_yaw: f32) {
+        self.orbit(delta_yaw, 0.0);
+    }
+
+    /// Gira alrededor del objetivo (yaw) y cambia la elevación (pitch)
+    pub fn orbit(&mut self, delta_yaw: f32, delta_pitch: f32) {
         self.yaw += delta_yaw;
-        
+        self.pitch = (self.pitch + delta_pitch).clamp(-Self::MAX_PITCH, Self::MAX_PITCH);
+
         // Recalcular posición relativa al target
-        let offset = Vec3::new(
-            self.distance_from_target * self.yaw.sin(),
-            self.position.y - self.target.y,
-            self.distance_from_target * self.yaw.cos(),
-        );
-        self.position = self.target + offset;
+        self.position = self.target + self.orbit_offset();
+    }
+
+    /// Multiplica la distancia al objetivo (< 1 acerca, > 1 aleja)
+    pub fn zoom(&mut self, factor: f32) {
+        self.set_distance(self.distance_from_target * factor);
+    }
+
+    /// Rayo (origen, dirección) que pasa por un píxel de la pantalla
+    ///
+    /// Des-proyecta el píxel con la inversa de view-projection.
+    pub fn screen_ray(&self, x: f32, y: f32, width: usize, height: usize) -> Option<(Vec3, Vec3)> {
+        let inverse_vp = self.view_projection_matrix().inverse()?;
+        let ndc_x = x / width as f32 * 2.0 - 1.0;
+        let ndc_y = 1.0 - y / height as f32 * 2.0; // Y invertido
+        let near = inverse_vp.transform_vec4(&Vec4::new(ndc_x, ndc_y, -1.0, 1.0));
+        let far = inverse_vp.transform_vec4(&Vec4::new(ndc_x, ndc_y, 1.0, 1.0));
+        let origin = near.perspective_divide();
+        Some((origin, (far.perspective_divide() - origin).normalize()))
+    }
+
+    /// Cuerpo bajo un píxel: el más cercano cuya esfera corta el rayo
+    ///
+    /// Los cuerpos lejanos se agrandan hasta `PICK_TOLERANCE` (radianes de
+    /// ángulo visual) para poder seleccionarlos aunque ocupen pocos píxeles.
+    pub fn pick(&self, x: f32, y: f32, width: usize, height: usize, spheres: &[(Vec3, f32)]) -> Option<usize> {
+        const PICK_TOLERANCE: f32 = 0.015;
+        let (origin, dir) = self.screen_ray(x, y, width, height)?;
+
+        let mut best: Option<(usize, f32)> = None;
+        for (i, &(center, radius)) in spheres.iter().enumerate() {
+            let to_center = center - origin;
+            let along = to_center.dot(&dir);
+            if along <= 0.0 {
+                continue; // Detrás de la cámara
+            }
+            let pick_radius = radius.max(along * PICK_TOLERANCE);
+            let miss2 = to_center.dot(&to_center) - along * along;
+            if miss2 > pick_radius * pick_radius {
+                continue;
+            }
+            // Distancia al punto de entrada en la esfera
+            let hit = along - (pick_radius * pick_radius - miss2).sqrt();
+            if best.is_none_or(|(_, distance)| hit < distance) {
+                best = Some((i, hit));
+            }
+        }
+        best.map(|(i, _)| i)
+    }
+
+    /// Cambia entre seguir un objetivo y el vuelo libre
+    ///
+    /// Al entrar en vuelo libre la nave parte de la vista actual y en reposo;
+    /// al volver a la órbita se retoma el objetivo con el horizonte recto.
+    pub fn set_mode(&mut self, mode: CameraMode) {
+        if mode == self.mode && self.warp.is_none() {
+            return;
+        }
+        self.warp = None;
+        self.collision_offset = Vec3::zero();
+        self.mode = mode;
+        match mode {
+            CameraMode::FreeFlight => {
+                self.orientation = Quat::look_rotation(self.target - self.position);
+                self.velocity = Vec3::zero();
+                self.update_free_view();
+            }
+            CameraMode::Orbit => {
+                self.up = Vec3::new(0.0, 1.0, 0.0);
+                self.look_at_target(self.target);
+            }
+        }
+    }
+
+    pub fn toggle_free_flight(&mut self) {
+        let mode = match self.mode {
+            CameraMode::Orbit => CameraMode::FreeFlight,
+            CameraMode::FreeFlight => CameraMode::Orbit,
+        };
+        self.set_mode(mode);
+    }
+
+    /// Avanza un frame de vuelo libre: giro, empuje e inercia
+    pub fn fly(&mut self, input: &FlightInput, delta_time: f32) {
+        if self.mode != CameraMode::FreeFlight {
+            return;
+        }
+        let flight = self.flight;
+
+        // Giros en ejes locales (yaw alrededor de Y, pitch de X, roll de Z)
+        let yaw = -input.yaw * flight.turn_speed * delta_time - input.look.0 * flight.mouse_sensitivity;
+        let pitch = input.pitch * flight.turn_speed * delta_time - input.look.1 * flight.mouse_sensitivity;
+        let roll = -input.roll * flight.roll_speed * delta_time;
+        self.orientation = (self.orientation
+            * Quat::from_axis_angle(Vec3::new(0.0, 1.0, 0.0), yaw)
+            * Quat::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), pitch)
+            * Quat::from_axis_angle(Vec3::new(0.0, 0.0, 1.0), roll))
+        .normalize();
+
+        // Empuje en ejes locales (z local apunta hacia atrás)
+        let local = Vec3::new(input.thrust.x, input.thrust.y, -input.thrust.z);
+        let mut thrust = flight.thrust;
+        if input.boost {
+            thrust *= flight.boost;
+        }
+        self.velocity = self.velocity + self.orientation.rotate(local) * (thrust * delta_time);
+
+        // Frenado exponencial (independiente de los FPS)
+        let damping = if input.brake { flight.brake } else { flight.damping };
+        self.velocity = self.velocity * (-damping * delta_time).exp();
+        let speed = self.velocity.length();
+        if speed > flight.max_speed {
+            self.velocity = self.velocity * (flight.max_speed / speed);
+        }
+
+        self.position = self.position + self.velocity * delta_time;
+        self.update_free_view();
+    }
+
+    /// Velocidad actual de la nave
+    pub fn speed(&self) -> f32 {
+        self.velocity.length()
+    }
+
+    /// Recalcula objetivo y vector arriba a partir de la orientación
+    fn update_free_view(&mut self) {
+        self.target = self.position + self.orientation.rotate(Vec3::new(0.0, 0.0, -1.0));
+        self.up = self.orientation.rotate(Vec3::new(0.0, 1.0, 0.0));
+    }
+
+    /// Viaja hasta un nuevo objetivo y termina siguiéndolo
+    ///
+    /// `obstacles` son las esferas (centro, radio) de los cuerpos que la
+    /// trayectoria debe evitar. Con `warp_duration` 0 el cambio es inmediato.
+    pub fn warp_to(
+        &mut self,
+        destination: usize,
+        position: Vec3,
+        radius: f32,
+        distance: f32,
+        obstacles: &[(Vec3, f32)],
+    ) {
+        self.target_radius = radius;
+        self.distance_from_target = self.clamp_distance(distance);
+        self.collision_offset = Vec3::zero();
+        self.mode = CameraMode::Orbit;
+        self.velocity = Vec3::zero();
+        if self.warp_duration <= 0.0 {
+            self.warp = None;
+            self.up = Vec3::new(0.0, 1.0, 0.0);
+            self.look_at_target(position);
+            return;
+        }
+        self.warp = Some(Warp::new(
+            destination,
+            self.warp_duration,
+            (self.position, self.target, self.up),
+            (position, self.orbit_offset()),
+            obstacles,
+        ));
+    }
+
+    /// Avanza el viaje en curso hacia la posición actual del destino
+    ///
+    /// Al llegar la cámara queda siguiendo al destino en modo órbita.
+    pub fn update_warp(&mut self, delta_time: f32, destination_position: Vec3) {
+        let warp = match &mut self.warp {
+            Some(warp) => warp,
+            None => return,
+        };
+        let (position, look, up) = warp.advance(delta_time, destination_position);
+        if warp.is_finished() {
+            self.warp = None;
+            self.up = Vec3::new(0.0, 1.0, 0.0);
+            self.look_at_target(destination_position);
+        } else {
+            self.position = position;
+            self.target = look;
+            self.up = up;
+        }
+    }
+
+    pub fn is_warping(&self) -> bool {
+        self.warp.is_some()
+    }
+
+    /// Efecto visual del viaje en curso (estelas y desenfoque)
+    pub fn warp_effect(&self) -> Option<WarpEffect> {
+        self.warp.as_ref().map(Warp::effect)
+    }
+
+    /// Posición de la cámara relativa al objetivo en modo órbita
+    pub fn orbit_offset(&self) -> Vec3 {
+        // Asegurar distancia mínima
+        let safe_distance = self.distance_from_target.max(5.0);
+        let horizontal = safe_distance * self.pitch.cos();
+        Vec3::new(
+            horizontal * self.yaw.sin(),
+            safe_distance * self.pitch.sin(), // Altura sobre el plano
+            horizontal * self.yaw.cos(),
+        )
     }
 
     /// Establece el objetivo de la cámara (para seguir planetas)
     pub fn look_at_target(&mut self, target: Vec3) {
         self.target = target;
-        // Asegurar distancia mínima
-        let safe_distance = self.distance_from_target.max(5.0);
         // Mantener distancia
-        let offset = Vec3::new(
-            safe_distance * self.yaw.sin(),
-            5.0, // Altura sobre el plano
-            safe_distance * self.yaw.cos(),
-        );
-        self.position = target + offset;
+        self.position = target + self.orbit_offset();
     }
 
     /// Cambia la distancia al objetivo (zoom)
     pub fn set_distance(&mut self, distance: f32) {
-        self.distance_from_target = distance.max(5.0).min(100.0);
+        self.distance_from_target = self.clamp_distance(distance);
         self.look_at_target(self.target);
     }
-}
\ No newline at end of file
+
+    /// Limita la distancia de órbita: nunca dentro de la capa segura del objetivo
+    fn clamp_distance(&self, distance: f32) -> f32 {
+        let min = self.collision.safe_distance(self.target_radius).max(5.0);
+        distance.clamp(min, 100.0_f32.max(min))
+    }
+
+    /// Aparta la cámara de los cuerpos (centro, radio) que tiene demasiado cerca
+    ///
+    /// Dentro de la capa segura la corrección es suave (sin saltos); la
+    /// distancia mínima sobre la superficie se respeta siempre. Se llama
+    /// después de mover la cámara en cada frame.
+    pub fn resolve_collisions(&mut self, spheres: &[(Vec3, f32)], delta_time: f32) {
+        if !self.collision.enabled || self.warp.is_some() {
+            self.collision_offset = Vec3::zero();
+            return;
+        }
+        let blend = self.collision.blend(delta_time);
+
+        match self.mode {
+            CameraMode::Orbit => {
+                // La posición base se recalcula cada frame: la corrección se
+                // guarda aparte y se acerca poco a poco a la necesaria
+                let push = self.collision.push_out(self.position, spheres);
+                self.collision_offset = self.collision_offset + (push - self.collision_offset) * blend;
+                self.position = self.collision.clamp(self.position + self.collision_offset, spheres);
+            }
+            CameraMode::FreeFlight => {
+                let push = self.collision.push_out(self.position, spheres);
+                self.position = self.collision.clamp(self.position + push * blend, spheres);
+
+                // Anular la velocidad hacia los cuerpos que se están tocando
+                for &(center, radius) in spheres {
+                    let offset = self.position - center;
+                    if offset.length() < self.collision.safe_distance(radius) {
+                        let normal = offset.normalize();
+                        let inward = self.velocity.dot(&normal);
+                        if inward < 0.0 {
+                            self.velocity = self.velocity - normal * inward;
+                        }
+                    }
+                }
+                self.update_free_view();
+            }
+        }
+    }
+
+    /// Copia el estado de la cámara
+    pub fn snapshot(&self) -> CameraSnapshot {
+        CameraSnapshot {
+            position: self.position,
+            target: self.target,
+            yaw: self.yaw,
+            pitch: self.pitch,
+            distance_from_target: self.distance_from_target,
+            fov: self.fov,
+            mode: self.mode,
+            orientation: self.orientation,
+            velocity: self.velocity,
+        }
+    }
+
+    /// Restaura un estado guardado con `snapshot`
+    pub fn restore(&mut self, snapshot: &CameraSnapshot) {
+        self.position = snapshot.position;
+        self.target = snapshot.target;
+        self.yaw = snapshot.yaw;
+        self.pitch = snapshot.pitch;
+        self.distance_from_target = snapshot.distance_from_target;
+        self.fov = snapshot.fov;
+        self.mode = snapshot.mode;
+        self.orientation = snapshot.orientation;
+        self.velocity = snapshot.velocity;
+        self.warp = None;
+        match self.mode {
+            CameraMode::FreeFlight => self.update_free_view(),
+            CameraMode::Orbit => self.up = Vec3::new(0.0, 1.0, 0.0),
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use crate::math::Vec3;
+
+/// Parámetros de la colisión de la cámara con los cuerpos
+///
+/// Alrededor de cada cuerpo hay una capa "segura" (proporcional al radio)
+/// de la que la cámara se aparta con suavidad, y un mínimo duro pegado a la
+/// superficie que nunca se cruza.
+#[derive(Debug, Clone, Copy)]
+pub struct CollisionSettings {
+    pub enabled: bool,
+    pub safe_altitude: f32, // Altura segura, en radios sobre la superficie
+    pub min_altitude: f32,  // Altura mínima absoluta, en radios
+    pub margin: f32,        // Distancia extra (unidades) para el plano cercano
+    pub stiffness: f32,     // Rapidez de la corrección suave (1/s)
+}
+
+impl Default for CollisionSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            safe_altitude: 0.6,
+            min_altitude: 0.1,
+            margin: 0.3,
+            stiffness: 6.0,
+        }
+    }
+}
+
+impl CollisionSettings {
+    /// Distancia al centro a partir de la cual no hay corrección
+    pub fn safe_distance(&self, radius: f32) -> f32 {
+        radius * (1.0 + self.safe_altitude) + self.margin
+    }
+
+    /// Distancia al centro que nunca se cruza
+    pub fn min_distance(&self, radius: f32) -> f32 {
+        radius * (1.0 + self.min_altitude) + self.margin
+    }
+
+    /// Desplazamiento que saca `position` de las capas seguras de `spheres`
+    pub fn push_out(&self, position: Vec3, spheres: &[(Vec3, f32)]) -> Vec3 {
+        let mut push = Vec3::zero();
+        for &(center, radius) in spheres {
+            let safe = self.safe_distance(radius);
+            let offset = position - center;
+            let distance = offset.length();
+            if distance < safe {
+                push = push + outward(offset) * (safe - distance);
+            }
+        }
+        push
+    }
+
+    /// Proyecta `position` fuera de la distancia mínima de cada cuerpo
+    pub fn clamp(&self, mut position: Vec3, spheres: &[(Vec3, f32)]) -> Vec3 {
+        for &(center, radius) in spheres {
+            let min = self.min_distance(radius);
+            let offset = position - center;
+            if offset.length() < min {
+                position = center + outward(offset) * min;
+            }
+        }
+        position
+    }
+
+    /// Fracción de la corrección suave a aplicar en `delta_time` segundos
+    pub fn blend(&self, delta_time: f32) -> f32 {
+        1.0 - (-self.stiffness * delta_time.max(0.0)).exp()
+    }
+}
+
+/// Dirección para salir de una esfera (arriba si se está justo en el centro)
+fn outward(offset: Vec3) -> Vec3 {
+    if offset.length() > 1e-5 {
+        offset.normalize()
+    } else {
+        Vec3::new(0.0, 1.0, 0.0)
+    }
+}
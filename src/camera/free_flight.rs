@@ -0,0 +1,70 @@
+use crate::math::Vec3;
+
+/// Modo de control de la cámara
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum CameraMode {
+    /// Sigue a un cuerpo girando a su alrededor
+    #[default]
+    Orbit,
+    /// Vuelo libre de seis grados de libertad (nave espacial)
+    FreeFlight,
+}
+
+impl CameraMode {
+    /// Nombre usado en los snapshots
+    pub fn name(&self) -> &'static str {
+        match self {
+            CameraMode::Orbit => "orbit",
+            CameraMode::FreeFlight => "free",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [CameraMode::Orbit, CameraMode::FreeFlight]
+            .into_iter()
+            .find(|m| m.name().eq_ignore_ascii_case(name))
+    }
+}
+
+/// Parámetros de la nave en vuelo libre
+#[derive(Debug, Clone, Copy)]
+pub struct FlightSettings {
+    pub thrust: f32,        // Aceleración de los propulsores (unidades/s²)
+    pub boost: f32,         // Multiplicador del empuje con turbo
+    pub damping: f32,       // Frenado pasivo por segundo (0 = inercia pura)
+    pub brake: f32,         // Frenado activo por segundo
+    pub max_speed: f32,
+    pub turn_speed: f32,    // Giro con teclado (radianes/s)
+    pub roll_speed: f32,    // Alabeo (radianes/s)
+    pub mouse_sensitivity: f32, // Radianes por píxel de mouse
+}
+
+impl Default for FlightSettings {
+    fn default() -> Self {
+        Self {
+            thrust: 15.0,
+            boost: 4.0,
+            damping: 0.4,
+            brake: 4.0,
+            max_speed: 80.0,
+            turn_speed: 1.2,
+            roll_speed: 1.5,
+            mouse_sensitivity: 0.004,
+        }
+    }
+}
+
+/// Controles de un frame de vuelo libre
+///
+/// Los ejes son locales a la nave: x derecha, y arriba, z adelante. Los
+/// valores de empuje y giro van de -1 a 1; `look` son píxeles de mouse.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FlightInput {
+    pub thrust: Vec3,
+    pub yaw: f32,         // Positivo = girar a la derecha
+    pub pitch: f32,       // Positivo = nariz arriba
+    pub roll: f32,        // Positivo = alabeo a la derecha
+    pub look: (f32, f32), // Desplazamiento del mouse (x, y) en píxeles
+    pub boost: bool,
+    pub brake: bool,
+}
@@ -1,2 +1,9 @@
+#[allow(clippy::module_inception)]
 pub mod camera;
-pub use camera::Camera;
\ No newline at end of file
+pub mod collision;
+pub mod free_flight;
+pub mod warp;
+pub use camera::{Camera, CameraSnapshot};
+pub use collision::CollisionSettings;
+pub use free_flight::{CameraMode, FlightInput, FlightSettings};
+pub use warp::{Warp, WarpEffect};
@@ -0,0 +1,169 @@
+use crate::math::Vec3;
+
+/// Distancia mínima entre la trayectoria y la superficie de un cuerpo,
+/// proporcional a su radio más un margen fijo
+const CLEARANCE_FACTOR: f32 = 1.5;
+const CLEARANCE_MARGIN: f32 = 1.0;
+
+/// Viaje animado de la cámara hacia un nuevo objetivo
+///
+/// La cámara recorre una curva de Bézier cúbica con aceleración y frenado
+/// suaves. El final de la curva sigue al cuerpo de destino mientras se mueve
+/// y los puntos de control se desvían para no atravesar otros cuerpos.
+#[derive(Debug, Clone)]
+pub struct Warp {
+    pub destination: usize, // Índice del cuerpo de destino
+    pub duration: f32,      // Segundos de viaje
+    elapsed: f32,
+    start_position: Vec3,
+    start_look: Vec3,
+    start_up: Vec3,
+    detour: Vec3,           // Desvío de los puntos de control
+    end_offset: Vec3,       // Posición final relativa al destino
+    direction: Vec3,        // Dirección de movimiento del último frame
+    speed: f32,             // Velocidad normalizada (0 a 1) para los efectos
+}
+
+/// Parámetros del efecto visual de warp para el render
+#[derive(Debug, Clone, Copy)]
+pub struct WarpEffect {
+    pub direction: Vec3, // Dirección de viaje (mundo, normalizada)
+    pub intensity: f32,  // 0 = sin efecto, 1 = velocidad máxima
+}
+
+impl Warp {
+    /// Prepara un viaje desde la vista actual
+    ///
+    /// `end_offset` es la posición de la cámara relativa al destino al llegar
+    /// y `obstacles` son las esferas (centro, radio) de todos los cuerpos.
+    pub fn new(
+        destination: usize,
+        duration: f32,
+        (start_position, start_look, start_up): (Vec3, Vec3, Vec3),
+        (destination_position, end_offset): (Vec3, Vec3),
+        obstacles: &[(Vec3, f32)],
+    ) -> Self {
+        let end = destination_position + end_offset;
+        Self {
+            destination,
+            duration: duration.max(0.001),
+            elapsed: 0.0,
+            start_position,
+            start_look,
+            start_up,
+            detour: find_detour(start_position, end, obstacles),
+            end_offset,
+            direction: (end - start_position).normalize(),
+            speed: 0.0,
+        }
+    }
+
+    /// Progreso del viaje entre 0 y 1
+    pub fn progress(&self) -> f32 {
+        (self.elapsed / self.duration).min(1.0)
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.elapsed >= self.duration
+    }
+
+    /// Avanza el viaje y devuelve (posición, punto de mira, arriba)
+    pub fn advance(&mut self, delta_time: f32, destination_position: Vec3) -> (Vec3, Vec3, Vec3) {
+        let previous = self.position_at(self.progress(), destination_position);
+        self.elapsed += delta_time.max(0.0);
+        let t = self.progress();
+        let position = self.position_at(t, destination_position);
+
+        let step = position - previous;
+        if step.length() > 1e-5 {
+            self.direction = step.normalize();
+        }
+        // Derivada de smootherstep: máximo 1.875 a mitad del viaje
+        self.speed = 30.0 * t * t * (1.0 - t) * (1.0 - t) / 1.875;
+
+        // La mirada gira hacia el destino durante la primera parte del viaje
+        let look_t = smootherstep((t / 0.6).min(1.0));
+        let look = self.start_look + (destination_position - self.start_look) * look_t;
+        let up = self.start_up + (Vec3::new(0.0, 1.0, 0.0) - self.start_up) * look_t;
+        (position, look, up.normalize())
+    }
+
+    /// Efecto visual del frame actual
+    pub fn effect(&self) -> WarpEffect {
+        WarpEffect {
+            direction: self.direction,
+            intensity: self.speed.clamp(0.0, 1.0),
+        }
+    }
+
+    fn position_at(&self, t: f32, destination_position: Vec3) -> Vec3 {
+        let end = destination_position + self.end_offset;
+        bezier(self.start_position, end, self.detour, smootherstep(t))
+    }
+}
+
+/// Curva suave 0 → 1 con velocidad y aceleración nulas en los extremos
+fn smootherstep(t: f32) -> f32 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+/// Bézier cúbica con los puntos de control a 1/3 y 2/3 de la recta,
+/// ambos desplazados por `detour` (desplazamiento en t: 3t(1-t) · detour)
+fn bezier(start: Vec3, end: Vec3, detour: Vec3, t: f32) -> Vec3 {
+    let chord = end - start;
+    let p1 = start + chord * (1.0 / 3.0) + detour;
+    let p2 = start + chord * (2.0 / 3.0) + detour;
+    let u = 1.0 - t;
+    start * (u * u * u) + p1 * (3.0 * u * u * t) + p2 * (3.0 * u * t * t) + end * (t * t * t)
+}
+
+/// Busca un desvío para que la curva no pase cerca de ningún cuerpo
+///
+/// Muestrea la curva, empuja el desvío lejos del cuerpo más invadido y
+/// repite hasta que la trayectoria queda libre (o se agotan los intentos).
+fn find_detour(start: Vec3, end: Vec3, obstacles: &[(Vec3, f32)]) -> Vec3 {
+    const SAMPLES: usize = 48;
+    let chord = end - start;
+    let chord_dir = chord.normalize();
+    let mut detour = Vec3::zero();
+
+    for _ in 0..16 {
+        // Mayor invasión: (profundidad, punto, centro, t)
+        let mut worst: Option<(f32, Vec3, Vec3, f32)> = None;
+        for i in 1..SAMPLES {
+            let t = i as f32 / SAMPLES as f32;
+            let point = bezier(start, end, detour, t);
+            for &(center, radius) in obstacles {
+                let clearance = radius * CLEARANCE_FACTOR + CLEARANCE_MARGIN;
+                // Los extremos pueden estar cerca (p. ej. el destino): solo se
+                // evitan cuerpos que la curva atraviesa lejos de sus extremos
+                if (start - center).length() < clearance || (end - center).length() < clearance {
+                    continue;
+                }
+                let depth = clearance - (point - center).length();
+                if depth > 0.0 && worst.is_none_or(|w| depth > w.0) {
+                    worst = Some((depth, point, center, t));
+                }
+            }
+        }
+
+        let (depth, point, center, t) = match worst {
+            Some(worst) => worst,
+            None => break,
+        };
+        // Empujar perpendicular a la recta, alejándose del centro del cuerpo
+        let away = point - center;
+        let mut push = away - chord_dir * away.dot(&chord_dir);
+        if push.length() < 1e-4 {
+            // El cuerpo está justo sobre la recta: rodearlo por arriba
+            let up = Vec3::new(0.0, 1.0, 0.0);
+            push = up - chord_dir * up.dot(&chord_dir);
+            if push.length() < 1e-4 {
+                push = Vec3::new(1.0, 0.0, 0.0);
+            }
+        }
+        let shape = (3.0 * t * (1.0 - t)).max(0.2);
+        detour = detour + push.normalize() * (depth * 1.1 / shape);
+    }
+    detour
+}
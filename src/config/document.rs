@@ -0,0 +1,671 @@
+use std::fmt;
+
+/// Error de un archivo de configuración, con la línea donde ocurrió
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConfigError {
+    pub line: usize, // 1-based; 0 si no corresponde a una línea concreta
+    pub message: String,
+}
+
+impl ConfigError {
+    pub fn new(line: usize, message: impl Into<String>) -> Self {
+        Self { line, message: message.into() }
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.line > 0 {
+            write!(f, "línea {}: {}", self.line, self.message)
+        } else {
+            write!(f, "{}", self.message)
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Valor de una clave (subconjunto de TOML)
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    String(String),
+    Number(f64),
+    Bool(bool),
+    Array(Vec<Value>),
+}
+
+impl Value {
+    fn type_name(&self) -> &'static str {
+        match self {
+            Value::String(_) => "texto",
+            Value::Number(_) => "número",
+            Value::Bool(_) => "booleano",
+            Value::Array(_) => "arreglo",
+        }
+    }
+}
+
+/// Par clave = valor con la línea donde aparece
+#[derive(Debug, Clone)]
+pub struct Entry {
+    pub key: String,
+    pub value: Value,
+    pub line: usize,
+}
+
+/// Tabla de claves: la raíz del documento, `[tabla]` o un elemento de `[[tabla]]`
+#[derive(Debug, Clone)]
+pub struct Table {
+    pub name: String,
+    pub line: usize, // Línea del encabezado (0 para la raíz)
+    pub entries: Vec<Entry>,
+}
+
+impl Table {
+    fn new(name: &str, line: usize) -> Self {
+        Self {
+            name: name.to_string(),
+            line,
+            entries: Vec::new(),
+        }
+    }
+
+    /// Obtiene una entrada por clave
+    pub fn get(&self, key: &str) -> Option<&Entry> {
+        self.entries.iter().find(|e| e.key == key)
+    }
+
+    /// Línea de una clave, o la del encabezado si la clave no existe
+    pub fn line_of(&self, key: &str) -> usize {
+        self.get(key).map(|e| e.line).unwrap_or(self.line)
+    }
+
+    /// Falla si la tabla contiene claves no permitidas
+    pub fn check_keys(&self, allowed: &[&str]) -> Result<(), ConfigError> {
+        match self.entries.iter().find(|e| !allowed.contains(&e.key.as_str())) {
+            Some(entry) => Err(ConfigError::new(
+                entry.line,
+                format!("clave desconocida '{}' en {}", entry.key, self.describe()),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Error de clave obligatoria ausente
+    pub fn missing(&self, key: &str) -> ConfigError {
+        ConfigError::new(self.line, format!("falta la clave '{}' en {}", key, self.describe()))
+    }
+
+    pub fn string(&self, key: &str) -> Result<Option<&str>, ConfigError> {
+        match self.get(key) {
+            Some(Entry { value: Value::String(s), .. }) => Ok(Some(s)),
+            Some(entry) => Err(type_error(entry, "texto")),
+            None => Ok(None),
+        }
+    }
+
+    pub fn f32(&self, key: &str) -> Result<Option<f32>, ConfigError> {
+        match self.get(key) {
+            Some(entry @ Entry { value: Value::Number(n), .. }) => narrow_f32(entry, *n).map(Some),
+            Some(entry) => Err(type_error(entry, "número")),
+            None => Ok(None),
+        }
+    }
+
+    /// Número con precisión doble (tiempos largos, contadores)
+    pub fn f64(&self, key: &str) -> Result<Option<f64>, ConfigError> {
+        match self.get(key) {
+            Some(Entry { value: Value::Number(n), .. }) => Ok(Some(*n)),
+            Some(entry) => Err(type_error(entry, "número")),
+            None => Ok(None),
+        }
+    }
+
+    pub fn bool(&self, key: &str) -> Result<Option<bool>, ConfigError> {
+        match self.get(key) {
+            Some(Entry { value: Value::Bool(b), .. }) => Ok(Some(*b)),
+            Some(entry) => Err(type_error(entry, "booleano")),
+            None => Ok(None),
+        }
+    }
+
+    /// Entero no negativo
+    pub fn usize(&self, key: &str) -> Result<Option<usize>, ConfigError> {
+        match self.get(key) {
+            Some(Entry { value: Value::Number(n), line, .. }) => {
+                if *n < 0.0 || n.fract() != 0.0 {
+                    Err(ConfigError::new(*line, format!("'{}' debe ser un entero no negativo", key)))
+                } else {
+                    Ok(Some(*n as usize))
+                }
+            }
+            Some(entry) => Err(type_error(entry, "entero")),
+            None => Ok(None),
+        }
+    }
+
+    /// Arreglo de exactamente N números
+    pub fn f32_array<const N: usize>(&self, key: &str) -> Result<Option<[f32; N]>, ConfigError> {
+        let entry = match self.get(key) {
+            Some(entry) => entry,
+            None => return Ok(None),
+        };
+        let wrong = || ConfigError::new(entry.line, format!("'{}' debe ser un arreglo de {} números", key, N));
+        match &entry.value {
+            Value::Array(items) if items.len() == N => {
+                let mut out = [0.0; N];
+                for (slot, item) in out.iter_mut().zip(items) {
+                    match item {
+                        Value::Number(n) => *slot = narrow_f32(entry, *n)?,
+                        _ => return Err(wrong()),
+                    }
+                }
+                Ok(Some(out))
+            }
+            _ => Err(wrong()),
+        }
+    }
+
+    /// Arreglo de textos
+    pub fn strings(&self, key: &str) -> Result<Option<Vec<&str>>, ConfigError> {
+        let entry = match self.get(key) {
+            Some(entry) => entry,
+            None => return Ok(None),
+        };
+        match &entry.value {
+            Value::Array(items) => items
+                .iter()
+                .map(|item| match item {
+                    Value::String(s) => Ok(s.as_str()),
+                    _ => Err(ConfigError::new(entry.line, format!("'{}' debe ser un arreglo de textos", key))),
+                })
+                .collect::<Result<Vec<_>, _>>()
+                .map(Some),
+            Value::String(s) => Ok(Some(vec![s.as_str()])),
+            _ => Err(type_error(entry, "arreglo de textos")),
+        }
+    }
+
+    fn describe(&self) -> String {
+        if self.name.is_empty() {
+            "la raíz".to_string()
+        } else {
+            format!("[{}]", self.name)
+        }
+    }
+}
+
+/// Convierte a f32; un valor finito que no entra en f32 daría infinito
+fn narrow_f32(entry: &Entry, n: f64) -> Result<f32, ConfigError> {
+    let value = n as f32;
+    if value.is_finite() {
+        Ok(value)
+    } else {
+        Err(ConfigError::new(entry.line, format!("'{}' está fuera de rango ({})", entry.key, n)))
+    }
+}
+
+fn type_error(entry: &Entry, expected: &str) -> ConfigError {
+    ConfigError::new(
+        entry.line,
+        format!("'{}' debe ser {}, no {}", entry.key, expected, entry.value.type_name()),
+    )
+}
+
+/// Documento de configuración (subconjunto de TOML)
+///
+/// Soporta comentarios `#`, `clave = valor` con textos, números, booleanos y
+/// arreglos en una sola línea, tablas `[nombre]` y arreglos de tablas `[[nombre]]`.
+#[derive(Debug, Clone)]
+pub struct Document {
+    pub root: Table,
+    pub tables: Vec<Table>, // En el orden del archivo
+}
+
+impl Document {
+    /// Interpreta el texto completo de un archivo
+    pub fn parse(text: &str) -> Result<Self, ConfigError> {
+        let mut root = Table::new("", 0);
+        let mut tables: Vec<Table> = Vec::new();
+        let mut single_tables: Vec<String> = Vec::new();
+
+        for (index, raw_line) in text.lines().enumerate() {
+            let line_number = index + 1;
+            let line = strip_comment(raw_line).trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            if let Some(rest) = line.strip_prefix("[[") {
+                let name = rest
+                    .strip_suffix("]]")
+                    .ok_or_else(|| ConfigError::new(line_number, "encabezado '[[...]]' sin cerrar"))?;
+                let name = parse_table_name(name, line_number)?;
+                if single_tables.iter().any(|t| t == name) {
+                    return Err(ConfigError::new(line_number, format!("'{}' ya se definió como [tabla]", name)));
+                }
+                tables.push(Table::new(name, line_number));
+                continue;
+            }
+
+            if let Some(rest) = line.strip_prefix('[') {
+                let name = rest
+                    .strip_suffix(']')
+                    .ok_or_else(|| ConfigError::new(line_number, "encabezado '[...]' sin cerrar"))?;
+                let name = parse_table_name(name, line_number)?;
+                if tables.iter().any(|t| t.name == name) {
+                    return Err(ConfigError::new(line_number, format!("tabla [{}] duplicada", name)));
+                }
+                single_tables.push(name.to_string());
+                tables.push(Table::new(name, line_number));
+                continue;
+            }
+
+            let (key, value_text) = line
+                .split_once('=')
+                .ok_or_else(|| ConfigError::new(line_number, format!("se esperaba 'clave = valor': {}", line)))?;
+            let key = key.trim();
+            if key.is_empty() || !key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+                return Err(ConfigError::new(line_number, format!("clave inválida '{}'", key)));
+            }
+
+            let mut parser = ValueParser::new(value_text.trim(), line_number);
+            let value = parser.parse_value()?;
+            parser.expect_end()?;
+
+            let table = tables.last_mut().unwrap_or(&mut root);
+            if table.get(key).is_some() {
+                return Err(ConfigError::new(line_number, format!("clave '{}' duplicada", key)));
+            }
+            table.entries.push(Entry {
+                key: key.to_string(),
+                value,
+                line: line_number,
+            });
+        }
+
+        Ok(Self { root, tables })
+    }
+
+    /// Primera tabla con ese nombre
+    pub fn table(&self, name: &str) -> Option<&Table> {
+        self.tables.iter().find(|t| t.name == name)
+    }
+
+    /// Todas las tablas con ese nombre (elementos de `[[nombre]]`)
+    pub fn tables_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Table> + 'a {
+        self.tables.iter().filter(move |t| t.name == name)
+    }
+
+    /// Falla si el documento contiene tablas no permitidas
+    pub fn check_tables(&self, allowed: &[&str]) -> Result<(), ConfigError> {
+        match self.tables.iter().find(|t| !allowed.contains(&t.name.as_str())) {
+            Some(table) => Err(ConfigError::new(table.line, format!("tabla desconocida [{}]", table.name))),
+            None => Ok(()),
+        }
+    }
+}
+
+fn parse_table_name(name: &str, line: usize) -> Result<&str, ConfigError> {
+    let name = name.trim();
+    if name.is_empty() || !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        return Err(ConfigError::new(line, format!("nombre de tabla inválido '{}'", name)));
+    }
+    Ok(name)
+}
+
+/// Elimina el comentario de una línea (ignora '#' dentro de textos)
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in line.char_indices() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if c == '\\' {
+                escaped = true;
+            } else if c == '"' {
+                in_string = false;
+            }
+        } else if c == '"' {
+            in_string = true;
+        } else if c == '#' {
+            return &line[..i];
+        }
+    }
+    line
+}
+
+/// Parser recursivo de valores en una línea
+struct ValueParser<'a> {
+    chars: std::iter::Peekable<std::str::CharIndices<'a>>,
+    text: &'a str,
+    line: usize,
+}
+
+impl<'a> ValueParser<'a> {
+    fn new(text: &'a str, line: usize) -> Self {
+        Self {
+            chars: text.char_indices().peekable(),
+            text,
+            line,
+        }
+    }
+
+    fn error(&self, message: impl Into<String>) -> ConfigError {
+        ConfigError::new(self.line, message)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.chars.peek(), Some((_, c)) if c.is_whitespace()) {
+            self.chars.next();
+        }
+    }
+
+    fn expect_end(&mut self) -> Result<(), ConfigError> {
+        self.skip_whitespace();
+        match self.chars.peek().copied() {
+            None => Ok(()),
+            Some((i, _)) => Err(self.error(format!("texto inesperado después del valor: '{}'", &self.text[i..]))),
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<Value, ConfigError> {
+        self.skip_whitespace();
+        match self.chars.peek().copied() {
+            None => Err(self.error("falta el valor")),
+            Some((_, '"')) => self.parse_string(),
+            Some((_, '[')) => self.parse_array(),
+            Some((start, _)) => {
+                let mut end = self.text.len();
+                while let Some(&(i, c)) = self.chars.peek() {
+                    if c == ',' || c == ']' || c.is_whitespace() {
+                        end = i;
+                        break;
+                    }
+                    self.chars.next();
+                }
+                let word = &self.text[start..end];
+                match word {
+                    "true" => Ok(Value::Bool(true)),
+                    "false" => Ok(Value::Bool(false)),
+                    _ => word
+                        .replace('_', "")
+                        .parse::<f64>()
+                        .ok()
+                        .filter(|n| n.is_finite())
+                        .map(Value::Number)
+                        .ok_or_else(|| self.error(format!("valor inválido '{}'", word))),
+                }
+            }
+        }
+    }
+
+    fn parse_string(&mut self) -> Result<Value, ConfigError> {
+        self.chars.next(); // Comilla inicial
+        let mut out = String::new();
+        while let Some((_, c)) = self.chars.next() {
+            match c {
+                '"' => return Ok(Value::String(out)),
+                '\\' => match self.chars.next() {
+                    Some((_, 'n')) => out.push('\n'),
+                    Some((_, 't')) => out.push('\t'),
+                    Some((_, '"')) => out.push('"'),
+                    Some((_, '\\')) => out.push('\\'),
+                    Some((_, other)) => return Err(self.error(format!("escape desconocido '\\{}'", other))),
+                    None => break,
+                },
+                _ => out.push(c),
+            }
+        }
+        Err(self.error("texto sin cerrar"))
+    }
+
+    fn parse_array(&mut self) -> Result<Value, ConfigError> {
+        self.chars.next(); // '['
+        let mut items = Vec::new();
+        loop {
+            self.skip_whitespace();
+            if let Some((_, ']')) = self.chars.peek() {
+                self.chars.next();
+                return Ok(Value::Array(items));
+            }
+            items.push(self.parse_value()?);
+            self.skip_whitespace();
+            match self.chars.next() {
+                Some((_, ',')) => continue,
+                Some((_, ']')) => return Ok(Value::Array(items)),
+                _ => return Err(self.error("arreglo sin cerrar (se esperaba ',' o ']')")),
+            }
+        }
+    }
+}
+
+/// Escritor de documentos en el mismo formato que lee `Document::parse`
+///
+/// Los números no finitos (NaN, infinito) no se pueden volver a leer: el
+/// primero que aparezca se informa en `finish`, así el error sale al guardar y
+/// no al cargar.
+#[derive(Debug, Default)]
+pub struct DocumentWriter {
+    out: String,
+    table: String,              // Tabla actual, para los mensajes de error
+    error: Option<ConfigError>, // Primer número no finito
+}
+
+impl DocumentWriter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn comment(&mut self, text: &str) -> &mut Self {
+        for line in text.lines() {
+            self.out.push_str("# ");
+            self.out.push_str(line);
+            self.out.push('\n');
+        }
+        self
+    }
+
+    /// Encabezado `[nombre]`
+    pub fn table(&mut self, name: &str) -> &mut Self {
+        self.out.push_str(&format!("\n[{}]\n", name));
+        self.table = format!("[{}]", name);
+        self
+    }
+
+    /// Encabezado `[[nombre]]`
+    pub fn array_table(&mut self, name: &str) -> &mut Self {
+        self.out.push_str(&format!("\n[[{}]]\n", name));
+        self.table = format!("[[{}]]", name);
+        self
+    }
+
+    pub fn string(&mut self, key: &str, value: &str) -> &mut Self {
+        self.out.push_str(&format!("{} = {}\n", key, quote(value)));
+        self
+    }
+
+    pub fn number(&mut self, key: &str, value: f32) -> &mut Self {
+        self.check_finite(key, value as f64);
+        self.out.push_str(&format!("{} = {}\n", key, format_number(value)));
+        self
+    }
+
+    pub fn number_f64(&mut self, key: &str, value: f64) -> &mut Self {
+        self.check_finite(key, value);
+        let text = format!("{:?}", value);
+        let text = if text.contains(['.', 'e', 'E']) { text } else { format!("{}.0", text) };
+        self.out.push_str(&format!("{} = {}\n", key, text));
+        self
+    }
+
+    pub fn integer(&mut self, key: &str, value: usize) -> &mut Self {
+        self.out.push_str(&format!("{} = {}\n", key, value));
+        self
+    }
+
+    pub fn bool(&mut self, key: &str, value: bool) -> &mut Self {
+        self.out.push_str(&format!("{} = {}\n", key, value));
+        self
+    }
+
+    pub fn numbers(&mut self, key: &str, values: &[f32]) -> &mut Self {
+        for value in values {
+            self.check_finite(key, *value as f64);
+        }
+        let items: Vec<String> = values.iter().map(|v| format_number(*v)).collect();
+        self.out.push_str(&format!("{} = [{}]\n", key, items.join(", ")));
+        self
+    }
+
+    pub fn strings(&mut self, key: &str, values: &[&str]) -> &mut Self {
+        let items: Vec<String> = values.iter().map(|v| quote(v)).collect();
+        self.out.push_str(&format!("{} = [{}]\n", key, items.join(", ")));
+        self
+    }
+
+    /// Texto del documento, o el primer número no finito que se intentó escribir
+    pub fn finish(&self) -> Result<String, ConfigError> {
+        match &self.error {
+            Some(err) => Err(err.clone()),
+            None => Ok(self.out.clone()),
+        }
+    }
+
+    fn check_finite(&mut self, key: &str, value: f64) {
+        if !value.is_finite() && self.error.is_none() {
+            let place = if self.table.is_empty() { String::new() } else { format!(" en {}", self.table) };
+            self.error = Some(ConfigError::new(
+                0,
+                format!("'{}'{} no es un número finito ({})", key, place, value),
+            ));
+        }
+    }
+}
+
+fn quote(value: &str) -> String {
+    let escaped = value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+        .replace('\t', "\\t");
+    format!("\"{}\"", escaped)
+}
+
+/// Formato más corto que conserva exactamente el f32 (siempre con punto decimal)
+fn format_number(value: f32) -> String {
+    let text = format!("{:?}", value);
+    if text.contains(['.', 'e', 'E']) || !value.is_finite() {
+        text
+    } else {
+        format!("{}.0", text)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error_line(text: &str) -> usize {
+        Document::parse(text).unwrap_err().line
+    }
+
+    #[test]
+    fn errors_report_their_line() {
+        assert_eq!(error_line("a = 1\n\n[tabla\n"), 3);
+        assert_eq!(error_line("a = 1\n# comentario\na = 2\n"), 3);
+        assert_eq!(error_line("[x]\nb = nada\n"), 2);
+        assert_eq!(error_line("a = [1, 2\n"), 1);
+        assert_eq!(error_line("a = 1\nb = \"sin cerrar\n"), 2);
+        assert_eq!(error_line("a = \"\\q\"\n"), 1);
+        assert_eq!(error_line("[x]\n[y]\n[x]\n"), 3);
+        assert_eq!(error_line("[x]\n\n[[x]]\n"), 3);
+        assert_eq!(error_line("clave sin valor\n"), 1);
+        assert_eq!(error_line("a = 1 2\n"), 1);
+    }
+
+    #[test]
+    fn type_and_key_errors_point_at_the_entry() {
+        let doc = Document::parse("[body]\nname = \"Sol\"\nradius = \"grande\"\nextra = 1\n").unwrap();
+        let table = doc.table("body").unwrap();
+        assert_eq!(table.f32("radius").unwrap_err().line, 3);
+        assert_eq!(table.check_keys(&["name", "radius"]).unwrap_err().line, 4);
+        assert_eq!(table.missing("color").line, 1);
+        assert_eq!(doc.check_tables(&["otra"]).unwrap_err().line, 1);
+    }
+
+    #[test]
+    fn strings_round_trip_with_escapes() {
+        let original = "comillas \" barra \\ # no es comentario\nsalto\ttab ñ";
+        let mut writer = DocumentWriter::new();
+        writer.string("texto", original).strings("lista", &[original, ""]);
+        let doc = Document::parse(&writer.finish().unwrap()).unwrap();
+        assert_eq!(doc.root.string("texto").unwrap(), Some(original));
+        assert_eq!(doc.root.strings("lista").unwrap(), Some(vec![original, ""]));
+    }
+
+    #[test]
+    fn comments_are_ignored_outside_strings() {
+        let doc = Document::parse("a = \"x # y\" # comentario\nb = 2 # otro\n").unwrap();
+        assert_eq!(doc.root.string("a").unwrap(), Some("x # y"));
+        assert_eq!(doc.root.f32("b").unwrap(), Some(2.0));
+    }
+
+    #[test]
+    fn arrays_of_tables_keep_order_and_entries() {
+        let text = "name = \"raíz\"\n\n[[body]]\nname = \"a\"\n\n[camera]\ndistance = 3\n\n[[body]]\nname = \"b\"\n";
+        let doc = Document::parse(text).unwrap();
+        let names: Vec<&str> = doc
+            .tables_named("body")
+            .map(|t| t.string("name").unwrap().unwrap())
+            .collect();
+        assert_eq!(names, ["a", "b"]);
+        assert_eq!(doc.tables_named("body").nth(1).unwrap().line, 9);
+        assert_eq!(doc.table("camera").unwrap().f32("distance").unwrap(), Some(3.0));
+        assert_eq!(doc.root.string("name").unwrap(), Some("raíz"));
+    }
+
+    #[test]
+    fn numbers_round_trip_exactly() {
+        let values = [0.1f32, -2.5, 1.0e-8, 123_456.79, 3.0, f32::MAX, f32::MIN_POSITIVE];
+        let mut writer = DocumentWriter::new();
+        writer.numbers("valores", &values).number_f64("doble", 0.1 + 0.2);
+        let doc = Document::parse(&writer.finish().unwrap()).unwrap();
+        let read = doc.root.f32_array::<7>("valores").unwrap().unwrap();
+        assert_eq!(read.map(f32::to_bits), values.map(f32::to_bits));
+        assert_eq!(doc.root.f64("doble").unwrap(), Some(0.1 + 0.2));
+    }
+
+    #[test]
+    fn non_finite_numbers_fail_when_writing() {
+        let mut writer = DocumentWriter::new();
+        writer.array_table("body").number("orbit_angle", f32::NAN);
+        let err = writer.finish().unwrap_err();
+        assert!(err.message.contains("orbit_angle"), "{}", err);
+        assert!(err.message.contains("[[body]]"), "{}", err);
+
+        let mut writer = DocumentWriter::new();
+        writer.numbers("color", &[1.0, f32::INFINITY]);
+        assert!(writer.finish().is_err());
+        let mut writer = DocumentWriter::new();
+        writer.number_f64("sim_time", f64::NEG_INFINITY);
+        assert!(writer.finish().is_err());
+
+        // Y tampoco se aceptan al leer
+        assert!(Document::parse("a = NaN\n").is_err());
+        assert!(Document::parse("a = inf\n").is_err());
+    }
+
+    #[test]
+    fn numbers_beyond_f32_are_rejected() {
+        let doc = Document::parse("radius = 1e39\ncolor = [1.0, -1e40, 0.0]\nmass = 3e38\n").unwrap();
+        let err = doc.root.f32("radius").unwrap_err();
+        assert_eq!(err.line, 1);
+        assert!(err.message.contains("radius"), "{}", err);
+        assert_eq!(doc.root.f32_array::<3>("color").unwrap_err().line, 2);
+        assert_eq!(doc.root.f32("mass").unwrap(), Some(3e38));
+        // En f64 siguen siendo válidos
+        assert_eq!(doc.root.f64("radius").unwrap(), Some(1e39));
+    }
+}
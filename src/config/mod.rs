@@ -0,0 +1,3 @@
+pub mod document;
+
+pub use document::{ConfigError, Document, DocumentWriter, Entry, Table, Value};
@@ -0,0 +1,246 @@
+/// Acción del usuario, independiente de la tecla que la dispara
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    // Cámara en modo órbita
+    ZoomIn,
+    ZoomOut,
+    RotateLeft,
+    RotateRight,
+
+    // Objetivos
+    NextTarget,
+    PrevTarget,
+    Target1,
+    Target2,
+    Target3,
+    Target4,
+    Target5,
+    Target6,
+    Target7,
+    Target8,
+    Target9,
+
+    // Vuelo libre
+    ToggleFreeFlight,
+    ThrustForward,
+    ThrustBack,
+    StrafeLeft,
+    StrafeRight,
+    ThrustUp,
+    ThrustDown,
+    LookLeft,
+    LookRight,
+    LookUp,
+    LookDown,
+    RollLeft,
+    RollRight,
+    Boost,
+    Brake,
+
+    // Tiempo y simulación
+    Pause,
+    Reverse,
+    Faster,
+    Slower,
+    NormalSpeed,
+    TogglePhysics,
+
+    // Aplicación
+    QuickSave,
+    QuickLoad,
+    ToggleHud,
+    ToggleHelp,
+    ToggleLabels,
+    RenderScaleUp,
+    RenderScaleDown,
+    ToggleAntialiasing,
+    CycleOrbitStyle,
+    Quit, // Siempre la última: los tests comprueban ALL contra ella
+}
+
+impl Action {
+    /// Todas las acciones, en el orden de la enumeración
+    pub const ALL: [Action; 46] = [
+        Action::ZoomIn,
+        Action::ZoomOut,
+        Action::RotateLeft,
+        Action::RotateRight,
+        Action::NextTarget,
+        Action::PrevTarget,
+        Action::Target1,
+        Action::Target2,
+        Action::Target3,
+        Action::Target4,
+        Action::Target5,
+        Action::Target6,
+        Action::Target7,
+        Action::Target8,
+        Action::Target9,
+        Action::ToggleFreeFlight,
+        Action::ThrustForward,
+        Action::ThrustBack,
+        Action::StrafeLeft,
+        Action::StrafeRight,
+        Action::ThrustUp,
+        Action::ThrustDown,
+        Action::LookLeft,
+        Action::LookRight,
+        Action::LookUp,
+        Action::LookDown,
+        Action::RollLeft,
+        Action::RollRight,
+        Action::Boost,
+        Action::Brake,
+        Action::Pause,
+        Action::Reverse,
+        Action::Faster,
+        Action::Slower,
+        Action::NormalSpeed,
+        Action::TogglePhysics,
+        Action::QuickSave,
+        Action::QuickLoad,
+        Action::ToggleHud,
+        Action::ToggleHelp,
+        Action::ToggleLabels,
+        Action::RenderScaleUp,
+        Action::RenderScaleDown,
+        Action::ToggleAntialiasing,
+        Action::CycleOrbitStyle,
+        Action::Quit,
+    ];
+
+    /// Acciones para elegir directamente un objetivo (1 a 9)
+    pub const TARGETS: [Action; 9] = [
+        Action::Target1,
+        Action::Target2,
+        Action::Target3,
+        Action::Target4,
+        Action::Target5,
+        Action::Target6,
+        Action::Target7,
+        Action::Target8,
+        Action::Target9,
+    ];
+
+    /// Nombre usado en el archivo de atajos
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::ZoomIn => "zoom_in",
+            Action::ZoomOut => "zoom_out",
+            Action::RotateLeft => "rotate_left",
+            Action::RotateRight => "rotate_right",
+            Action::NextTarget => "next_target",
+            Action::PrevTarget => "prev_target",
+            Action::Target1 => "target_1",
+            Action::Target2 => "target_2",
+            Action::Target3 => "target_3",
+            Action::Target4 => "target_4",
+            Action::Target5 => "target_5",
+            Action::Target6 => "target_6",
+            Action::Target7 => "target_7",
+            Action::Target8 => "target_8",
+            Action::Target9 => "target_9",
+            Action::ToggleFreeFlight => "toggle_free_flight",
+            Action::ThrustForward => "thrust_forward",
+            Action::ThrustBack => "thrust_back",
+            Action::StrafeLeft => "strafe_left",
+            Action::StrafeRight => "strafe_right",
+            Action::ThrustUp => "thrust_up",
+            Action::ThrustDown => "thrust_down",
+            Action::LookLeft => "look_left",
+            Action::LookRight => "look_right",
+            Action::LookUp => "look_up",
+            Action::LookDown => "look_down",
+            Action::RollLeft => "roll_left",
+            Action::RollRight => "roll_right",
+            Action::Boost => "boost",
+            Action::Brake => "brake",
+            Action::Pause => "pause",
+            Action::Reverse => "reverse",
+            Action::Faster => "faster",
+            Action::Slower => "slower",
+            Action::NormalSpeed => "normal_speed",
+            Action::TogglePhysics => "toggle_physics",
+            Action::QuickSave => "quicksave",
+            Action::QuickLoad => "quickload",
+            Action::ToggleHud => "toggle_hud",
+            Action::ToggleHelp => "toggle_help",
+            Action::ToggleLabels => "toggle_labels",
+            Action::RenderScaleUp => "render_scale_up",
+            Action::RenderScaleDown => "render_scale_down",
+            Action::ToggleAntialiasing => "toggle_antialiasing",
+            Action::CycleOrbitStyle => "cycle_orbit_style",
+            Action::Quit => "quit",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|a| a.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Descripción corta para la ayuda
+    pub fn description(&self) -> &'static str {
+        match self {
+            Action::ZoomIn => "Acercar cámara",
+            Action::ZoomOut => "Alejar cámara",
+            Action::RotateLeft => "Rotar cámara izquierda",
+            Action::RotateRight => "Rotar cámara derecha",
+            Action::NextTarget => "Siguiente objetivo",
+            Action::PrevTarget => "Objetivo anterior",
+            Action::Target1
+            | Action::Target2
+            | Action::Target3
+            | Action::Target4
+            | Action::Target5
+            | Action::Target6
+            | Action::Target7
+            | Action::Target8
+            | Action::Target9 => "Viajar a un objetivo (orden de la escena)",
+            Action::ToggleFreeFlight => "Alternar vuelo libre / seguir objetivo",
+            Action::ThrustForward => "Vuelo libre: empuje adelante",
+            Action::ThrustBack => "Vuelo libre: empuje atrás",
+            Action::StrafeLeft => "Vuelo libre: empuje a la izquierda",
+            Action::StrafeRight => "Vuelo libre: empuje a la derecha",
+            Action::ThrustUp => "Vuelo libre: subir",
+            Action::ThrustDown => "Vuelo libre: bajar",
+            Action::LookLeft => "Vuelo libre: girar a la izquierda",
+            Action::LookRight => "Vuelo libre: girar a la derecha",
+            Action::LookUp => "Vuelo libre: nariz arriba",
+            Action::LookDown => "Vuelo libre: nariz abajo",
+            Action::RollLeft => "Vuelo libre: alabeo izquierda",
+            Action::RollRight => "Vuelo libre: alabeo derecha",
+            Action::Boost => "Vuelo libre: turbo",
+            Action::Brake => "Vuelo libre: frenar",
+            Action::Pause => "Pausar / reanudar la simulación",
+            Action::Reverse => "Invertir el sentido del tiempo",
+            Action::Faster => "Acelerar el tiempo",
+            Action::Slower => "Frenar el tiempo",
+            Action::NormalSpeed => "Velocidad normal (1x)",
+            Action::TogglePhysics => "Alternar gravedad N-cuerpos / órbitas fijas",
+            Action::QuickSave => "Guardar snapshot",
+            Action::QuickLoad => "Cargar snapshot",
+            Action::ToggleHud => "Mostrar / ocultar el HUD",
+            Action::ToggleHelp => "Mostrar / ocultar esta ayuda",
+            Action::ToggleLabels => "Mostrar / ocultar los nombres de los cuerpos",
+            Action::RenderScaleUp => "Subir la escala de render (más calidad)",
+            Action::RenderScaleDown => "Bajar la escala de render (más velocidad)",
+            Action::ToggleAntialiasing => "Cambiar el anti-aliasing (sin / MSAA 2x / 4x)",
+            Action::CycleOrbitStyle => "Estilo de las órbitas detrás de su centro",
+            Action::Quit => "Salir",
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn all_lists_every_variant_in_order() {
+        assert_eq!(Action::ALL.len(), Action::Quit as usize + 1);
+        for (i, action) in Action::ALL.into_iter().enumerate() {
+            assert_eq!(action as usize, i, "{:?} fuera de orden", action);
+            assert_eq!(Action::from_name(action.name()), Some(action));
+        }
+    }
+}
@@ -0,0 +1,353 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+
+use minifb::{Key, KeyRepeat, Window};
+
+use crate::config::{ConfigError, Document, DocumentWriter};
+use super::action::Action;
+
+/// Teclas que se pueden asignar, con el nombre de minifb (`Key1`, `Tab`, ...)
+const KEYS: [Key; 106] = [
+    Key::Key0, Key::Key1, Key::Key2, Key::Key3, Key::Key4,
+    Key::Key5, Key::Key6, Key::Key7, Key::Key8, Key::Key9,
+    Key::A, Key::B, Key::C, Key::D, Key::E, Key::F, Key::G, Key::H, Key::I,
+    Key::J, Key::K, Key::L, Key::M, Key::N, Key::O, Key::P, Key::Q, Key::R,
+    Key::S, Key::T, Key::U, Key::V, Key::W, Key::X, Key::Y, Key::Z,
+    Key::F1, Key::F2, Key::F3, Key::F4, Key::F5, Key::F6, Key::F7, Key::F8,
+    Key::F9, Key::F10, Key::F11, Key::F12, Key::F13, Key::F14, Key::F15,
+    Key::Down, Key::Left, Key::Right, Key::Up,
+    Key::Apostrophe, Key::Backquote, Key::Backslash, Key::Comma, Key::Equal,
+    Key::LeftBracket, Key::Minus, Key::Period, Key::RightBracket, Key::Semicolon,
+    Key::Slash, Key::Backspace, Key::Delete, Key::End, Key::Enter, Key::Escape,
+    Key::Home, Key::Insert, Key::Menu, Key::PageDown, Key::PageUp, Key::Pause,
+    Key::Space, Key::Tab, Key::NumLock, Key::CapsLock, Key::ScrollLock,
+    Key::LeftShift, Key::RightShift, Key::LeftCtrl, Key::RightCtrl,
+    Key::NumPad0, Key::NumPad1, Key::NumPad2, Key::NumPad3, Key::NumPad4,
+    Key::NumPad5, Key::NumPad6, Key::NumPad7, Key::NumPad8, Key::NumPad9,
+    Key::NumPadDot, Key::NumPadSlash, Key::NumPadAsterisk, Key::NumPadMinus,
+    Key::NumPadPlus, Key::NumPadEnter,
+    Key::LeftAlt, Key::RightAlt, Key::LeftSuper, Key::RightSuper,
+];
+
+/// Nombre de una tecla en el archivo de atajos
+pub fn key_name(key: Key) -> String {
+    format!("{:?}", key)
+}
+
+/// Tecla a partir de su nombre (sin distinguir mayúsculas; "1" = `Key1`)
+pub fn key_from_name(name: &str) -> Option<Key> {
+    if name.len() == 1 && name.as_bytes()[0].is_ascii_digit() {
+        return key_from_name(&format!("Key{}", name));
+    }
+    KEYS.into_iter().find(|&key| key_name(key).eq_ignore_ascii_case(name))
+}
+
+/// Tecla con modificadores opcionales, p. ej. "Shift+Tab"
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeyCombo {
+    pub key: Key,
+    pub shift: bool,
+    pub ctrl: bool,
+    pub alt: bool,
+}
+
+impl KeyCombo {
+    pub fn new(key: Key) -> Self {
+        Self { key, shift: false, ctrl: false, alt: false }
+    }
+
+    pub fn with_shift(key: Key) -> Self {
+        Self { shift: true, ..Self::new(key) }
+    }
+
+    /// Interpreta "Tecla" o "Mod+...+Tecla" (modificadores: Shift, Ctrl, Alt)
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut parts: Vec<&str> = text.split('+').map(str::trim).collect();
+        let key_part = parts.pop().unwrap_or_default();
+        let key = key_from_name(key_part).ok_or_else(|| format!("tecla desconocida '{}'", key_part))?;
+
+        let mut combo = Self::new(key);
+        for modifier in parts {
+            match modifier.to_ascii_lowercase().as_str() {
+                "shift" => combo.shift = true,
+                "ctrl" => combo.ctrl = true,
+                "alt" => combo.alt = true,
+                _ => return Err(format!("modificador desconocido '{}' (Shift, Ctrl o Alt)", modifier)),
+            }
+        }
+        Ok(combo)
+    }
+
+    fn modifier_count(&self) -> usize {
+        self.shift as usize + self.ctrl as usize + self.alt as usize
+    }
+
+    /// Los modificadores que pide la combinación están apretados
+    fn modifiers_held(&self, held: &Modifiers) -> bool {
+        (!self.shift || held.shift) && (!self.ctrl || held.ctrl) && (!self.alt || held.alt)
+    }
+}
+
+impl fmt::Display for KeyCombo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.ctrl {
+            write!(f, "Ctrl+")?;
+        }
+        if self.alt {
+            write!(f, "Alt+")?;
+        }
+        if self.shift {
+            write!(f, "Shift+")?;
+        }
+        write!(f, "{}", key_name(self.key))
+    }
+}
+
+/// Modificadores apretados en este frame
+struct Modifiers {
+    shift: bool,
+    ctrl: bool,
+    alt: bool,
+}
+
+impl Modifiers {
+    fn read(window: &Window) -> Self {
+        let down = |a, b| window.is_key_down(a) || window.is_key_down(b);
+        Self {
+            shift: down(Key::LeftShift, Key::RightShift),
+            ctrl: down(Key::LeftCtrl, Key::RightCtrl),
+            alt: down(Key::LeftAlt, Key::RightAlt),
+        }
+    }
+}
+
+/// Asignación de teclas a acciones
+///
+/// Una acción puede tener varias combinaciones (o ninguna) y una tecla puede
+/// usarse en varias acciones (p. ej. W acerca en órbita y acelera en vuelo
+/// libre). Si dos combinaciones usan la misma tecla, gana la que pide más
+/// modificadores: con Shift apretado, Shift+Tab no dispara también Tab.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KeyBindings {
+    combos: Vec<Vec<KeyCombo>>, // Indexado por `Action as usize`
+}
+
+impl KeyBindings {
+    /// Ruta por defecto del archivo de atajos
+    pub const DEFAULT_PATH: &'static str = "assets/keybindings.toml";
+
+    /// Combinaciones de una acción
+    pub fn get(&self, action: Action) -> &[KeyCombo] {
+        &self.combos[action as usize]
+    }
+
+    /// Reemplaza las combinaciones de una acción
+    pub fn set(&mut self, action: Action, combos: Vec<KeyCombo>) {
+        self.combos[action as usize] = combos;
+    }
+
+    /// Texto legible de los atajos de una acción, p. ej. "Equal / NumPadPlus"
+    pub fn describe(&self, action: Action) -> String {
+        let combos: Vec<String> = self.get(action).iter().map(|c| c.to_string()).collect();
+        if combos.is_empty() {
+            "(sin asignar)".to_string()
+        } else {
+            combos.join(" / ")
+        }
+    }
+
+    /// Filas (teclas, descripción) de la ayuda de controles
+    ///
+    /// Los objetivos directos (1 a 9) se resumen en una sola fila.
+    pub fn help(&self) -> Vec<(String, String)> {
+        let mut rows = Vec::new();
+        for action in Action::ALL {
+            if action == Action::Target1 {
+                let keys: Vec<String> = Action::TARGETS.iter().map(|&a| self.describe(a)).collect();
+                rows.push((keys.join(" "), action.description().to_string()));
+            } else if !Action::TARGETS.contains(&action) {
+                rows.push((self.describe(action), action.description().to_string()));
+            }
+        }
+        rows
+    }
+
+    /// La acción se mantiene (teclas mantenidas, como el zoom)
+    pub fn is_down(&self, window: &Window, action: Action) -> bool {
+        self.matches(window, action, |key| window.is_key_down(key))
+    }
+
+    /// La acción se disparó en este frame (sin repetición)
+    pub fn is_pressed(&self, window: &Window, action: Action) -> bool {
+        self.matches(window, action, |key| window.is_key_pressed(key, KeyRepeat::No))
+    }
+
+    /// Eje entre dos acciones: 1, -1 o 0 (ambas o ninguna)
+    pub fn axis(&self, window: &Window, positive: Action, negative: Action) -> f32 {
+        let mut value = 0.0;
+        if self.is_down(window, positive) {
+            value += 1.0;
+        }
+        if self.is_down(window, negative) {
+            value -= 1.0;
+        }
+        value
+    }
+
+    fn matches(&self, window: &Window, action: Action, key_active: impl Fn(Key) -> bool) -> bool {
+        let held = Modifiers::read(window);
+        self.get(action).iter().any(|combo| {
+            key_active(combo.key)
+                && combo.modifiers_held(&held)
+                && !self.shadowed(combo, &held)
+        })
+    }
+
+    /// Otra combinación con la misma tecla y más modificadores está activa
+    fn shadowed(&self, combo: &KeyCombo, held: &Modifiers) -> bool {
+        self.combos.iter().flatten().any(|other| {
+            other.key == combo.key
+                && other.modifier_count() > combo.modifier_count()
+                && other.modifiers_held(held)
+        })
+    }
+
+    /// Carga los atajos de un archivo; las acciones que no aparecen
+    /// conservan sus teclas por defecto
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let text = fs::read_to_string(path)
+            .map_err(|err| ConfigError::new(0, err.to_string()))?;
+        Self::parse(&text)
+    }
+
+    /// Interpreta el texto de un archivo de atajos
+    ///
+    /// ```toml
+    /// [bindings]
+    /// zoom_in = ["W", "Up"]
+    /// prev_target = "Shift+Tab"
+    /// quicksave = []             # sin asignar
+    /// ```
+    pub fn parse(text: &str) -> Result<Self, ConfigError> {
+        let document = Document::parse(text)?;
+        document.check_tables(&["bindings"])?;
+        document.root.check_keys(&[])?;
+
+        let mut bindings = Self::default();
+        let table = match document.table("bindings") {
+            Some(table) => table,
+            None => return Ok(bindings),
+        };
+        for entry in &table.entries {
+            let action = Action::from_name(&entry.key).ok_or_else(|| {
+                ConfigError::new(entry.line, format!("acción desconocida '{}'", entry.key))
+            })?;
+            let names = table.strings(&entry.key)?.unwrap_or_default();
+            let combos = names
+                .iter()
+                .map(|name| KeyCombo::parse(name).map_err(|message| ConfigError::new(entry.line, message)))
+                .collect::<Result<Vec<_>, _>>()?;
+            bindings.set(action, combos);
+        }
+        Ok(bindings)
+    }
+
+    /// Escribe todos los atajos en el formato de `parse`
+    pub fn to_config_string(&self) -> String {
+        let mut writer = DocumentWriter::new();
+        writer
+            .comment("Atajos de teclado de Space Travel")
+            .comment("Cada acción acepta una tecla o un arreglo de teclas; [] la deja sin asignar.")
+            .comment("Nombres de teclas: A-Z, Key0-Key9 (o 0-9), F1-F15, Up, Down, Left, Right,")
+            .comment("Space, Tab, Enter, Escape, LeftShift, LeftCtrl, Equal, Minus, NumPadPlus, ...")
+            .comment("Modificadores: \"Shift+Tab\", \"Ctrl+S\", \"Alt+F4\".")
+            .table("bindings");
+        for action in Action::ALL {
+            let combos: Vec<String> = self.get(action).iter().map(|c| c.to_string()).collect();
+            let combos: Vec<&str> = combos.iter().map(String::as_str).collect();
+            writer.strings(action.name(), &combos);
+        }
+        writer.finish().expect("los atajos no tienen números")
+    }
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        let mut bindings = Self {
+            combos: vec![Vec::new(); Action::ALL.len()],
+        };
+        let keys = |keys: &[Key]| keys.iter().map(|&k| KeyCombo::new(k)).collect::<Vec<_>>();
+
+        bindings.set(Action::ZoomIn, keys(&[Key::W]));
+        bindings.set(Action::ZoomOut, keys(&[Key::S]));
+        bindings.set(Action::RotateLeft, keys(&[Key::A]));
+        bindings.set(Action::RotateRight, keys(&[Key::D]));
+        bindings.set(Action::NextTarget, keys(&[Key::Tab]));
+        bindings.set(Action::PrevTarget, vec![KeyCombo::with_shift(Key::Tab)]);
+        let number_keys = [
+            Key::Key1, Key::Key2, Key::Key3, Key::Key4, Key::Key5,
+            Key::Key6, Key::Key7, Key::Key8, Key::Key9,
+        ];
+        for (action, key) in Action::TARGETS.into_iter().zip(number_keys) {
+            bindings.set(action, keys(&[key]));
+        }
+
+        bindings.set(Action::ToggleFreeFlight, keys(&[Key::C]));
+        bindings.set(Action::ThrustForward, keys(&[Key::W]));
+        bindings.set(Action::ThrustBack, keys(&[Key::S]));
+        bindings.set(Action::StrafeLeft, keys(&[Key::A]));
+        bindings.set(Action::StrafeRight, keys(&[Key::D]));
+        bindings.set(Action::ThrustUp, keys(&[Key::Space]));
+        bindings.set(Action::ThrustDown, keys(&[Key::LeftCtrl]));
+        bindings.set(Action::LookLeft, keys(&[Key::Left]));
+        bindings.set(Action::LookRight, keys(&[Key::Right]));
+        bindings.set(Action::LookUp, keys(&[Key::Up]));
+        bindings.set(Action::LookDown, keys(&[Key::Down]));
+        bindings.set(Action::RollLeft, keys(&[Key::Q]));
+        bindings.set(Action::RollRight, keys(&[Key::E]));
+        bindings.set(Action::Boost, keys(&[Key::LeftShift]));
+        bindings.set(Action::Brake, keys(&[Key::X]));
+
+        bindings.set(Action::Pause, keys(&[Key::P]));
+        bindings.set(Action::Reverse, keys(&[Key::R]));
+        bindings.set(Action::Faster, keys(&[Key::Equal, Key::NumPadPlus]));
+        bindings.set(Action::Slower, keys(&[Key::Minus, Key::NumPadMinus]));
+        bindings.set(Action::NormalSpeed, keys(&[Key::Key0]));
+        bindings.set(Action::TogglePhysics, keys(&[Key::G]));
+
+        bindings.set(Action::QuickSave, keys(&[Key::F5]));
+        bindings.set(Action::QuickLoad, keys(&[Key::F9]));
+        bindings.set(Action::ToggleHud, keys(&[Key::H]));
+        bindings.set(Action::ToggleHelp, keys(&[Key::F1]));
+        bindings.set(Action::ToggleLabels, keys(&[Key::L]));
+        bindings.set(Action::RenderScaleUp, keys(&[Key::PageUp]));
+        bindings.set(Action::RenderScaleDown, keys(&[Key::PageDown]));
+        bindings.set(Action::ToggleAntialiasing, keys(&[Key::M]));
+        bindings.set(Action::CycleOrbitStyle, keys(&[Key::O]));
+        bindings.set(Action::Quit, keys(&[Key::Escape]));
+        bindings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ASSET: &str = include_str!("../../assets/keybindings.toml");
+
+    #[test]
+    fn asset_matches_defaults() {
+        // Se regenera con `cargo run --example generate_keybindings`
+        assert_eq!(ASSET, KeyBindings::default().to_config_string());
+        assert_eq!(KeyBindings::parse(ASSET).unwrap(), KeyBindings::default());
+    }
+
+    #[test]
+    fn config_string_round_trips() {
+        let mut bindings = KeyBindings::default();
+        bindings.set(Action::Quit, Vec::new());
+        bindings.set(Action::Pause, vec![KeyCombo::with_shift(Key::P), KeyCombo::new(Key::F2)]);
+        assert_eq!(KeyBindings::parse(&bindings.to_config_string()).unwrap(), bindings);
+    }
+}
@@ -0,0 +1,5 @@
+pub mod action;
+pub mod bindings;
+
+pub use action::Action;
+pub use bindings::{KeyBindings, KeyCombo};
@@ -3,4 +3,6 @@ pub mod renderer;
 pub mod camera;
 pub mod scene;
 pub mod threading;
-pub mod shaders;
\ No newline at end of file
+pub mod shaders;
+pub mod config;
+pub mod input;
@@ -1,352 +1,666 @@
-mod math;
-mod renderer;
-mod camera;
-mod scene;
-mod threading;
-mod shaders;
-
-use minifb::{Key, Window, WindowOptions};
+use minifb::{MouseButton, MouseMode, Window, WindowOptions};
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
-use math::{Vec3, Mat4};
-use renderer::{Framebuffer, draw_line, rgb_to_u32, render_skybox};
-use camera::Camera;
-use scene::SolarSystem;
-use shaders::{ShaderType, FragmentData, apply_shader};
+use solar2::renderer::{Antialiasing, Hud, ImageFormat, OrbitBackStyle, Pipeline, RenderTarget, SkyBackground, save_frame};
+use solar2::camera::{Camera, CameraMode, FlightInput};
+use solar2::input::{Action, KeyBindings};
+use solar2::math::Vec3;
+use solar2::scene::{SimulationClock, Snapshot, SolarSystem, load_scene, load_snapshot, save_scene, save_snapshot};
 
-const WIDTH: usize = 800;
+const WIDTH: usize = 800; // Tamaño inicial de la ventana sin --width / --height
 const HEIGHT: usize = 600;
+const QUICKSAVE_PATH: &str = "snapshots/quicksave.toml"; // F5 / F9 sin --snapshot
 
 fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let options = match Options::parse(&args) {
+        Ok(options) => options,
+        Err(message) => {
+            eprintln!("Error: {}", message);
+            eprintln!("{}", Options::USAGE);
+            std::process::exit(2);
+        }
+    };
+
+    // Snapshot, escena desde archivo o la escena por defecto
+    let (mut solar_system, snapshot) = if let Some(path) = &options.snapshot {
+        let (system, snapshot) = load_snapshot(path).unwrap_or_else(|err| {
+            eprintln!("No se pudo cargar el snapshot: {}", err);
+            std::process::exit(1);
+        });
+        (system, Some(snapshot))
+    } else {
+        let system = match &options.scene {
+            Some(path) => load_scene(path).unwrap_or_else(|err| {
+                eprintln!("No se pudo cargar la escena: {}", err);
+                std::process::exit(1);
+            }),
+            None => SolarSystem::new(),
+        };
+        (system, None)
+    };
+    if options.physics && !solar_system.physics_enabled() {
+        solar_system.set_physics(true);
+    }
+
+    // Fondo: procedural o una imagen desde disco
+    let mut pipeline = Pipeline::new();
+    pipeline.antialiasing = options.antialiasing;
+    pipeline.orbit_back = options.orbit_back;
+    if let Some(path) = &options.skybox {
+        pipeline.sky = SkyBackground::load(path).unwrap_or_else(|err| {
+            eprintln!("No se pudo cargar el skybox: {}", err);
+            std::process::exit(1);
+        });
+    }
+
+    let solar_system = if options.headless {
+        run_headless(&options, &pipeline, solar_system, snapshot)
+    } else {
+        run_window(&options, &mut pipeline, solar_system, snapshot)
+    };
+
+    if let Some(path) = &options.save_scene {
+        write_scene(&solar_system, path);
+    }
+}
+
+/// Guarda el estado final del sistema como archivo de escena
+fn write_scene(solar_system: &SolarSystem, path: &Path) {
+    match save_scene(solar_system, path) {
+        Ok(()) => println!("Escena guardada en {}", path.display()),
+        Err(err) => {
+            eprintln!("No se pudo guardar la escena: {}", err);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Loop interactivo con ventana
+///
+/// `snapshot` es el estado inicial opcional; F5/F9 guardan y cargan el
+/// archivo de `--snapshot` (o `QUICKSAVE_PATH`).
+fn run_window(options: &Options, pipeline: &mut Pipeline, mut solar_system: SolarSystem, snapshot: Option<Snapshot>) -> SolarSystem {
+    let quicksave = options
+        .snapshot
+        .clone()
+        .unwrap_or_else(|| PathBuf::from(QUICKSAVE_PATH));
+    let bindings = load_bindings(options);
+
     // Crear ventana
     let mut window = Window::new(
         "Space Travel - Sistema Solar",
-        WIDTH,
-        HEIGHT,
+        options.width,
+        options.height,
         WindowOptions {
-            resize: false,
+            resize: true,
             ..WindowOptions::default()
         },
     )
     .expect("No se pudo crear la ventana");
 
     // Limitar a ~60 FPS
-    window.limit_update_rate(Some(std::time::Duration::from_micros(16600)));
+    window.set_target_fps(60);
 
     // Inicializar componentes
-    let mut framebuffer = Framebuffer::new(WIDTH, HEIGHT);
-    let mut camera = Camera::new(WIDTH as f32 / HEIGHT as f32);
-    let mut solar_system = SolarSystem::new();
+    let mut target = RenderTarget::new(options.width, options.height, options.render_scale);
+    let mut camera = Camera::new(target.aspect());
+    if let Some(duration) = options.warp {
+        camera.warp_duration = duration;
+    }
+    let mut clock = SimulationClock::default();
 
     // Estado
     let mut last_time = Instant::now();
-    let mut current_target: usize = 0; // Índice del planeta que sigue la cámara
+    let mut title = String::new();
+    let mut current_target = solar_system.camera_target; // Índice del planeta que sigue la cámara
     let mut total_time: f32 = 0.0; // Tiempo total para animaciones de shaders
+    let mut mouse = MouseTracker::default(); // Arrastre, clic y rueda
+
+    // Posición inicial de la cámara (definida por la escena)
+    camera.target_radius = solar_system.bodies[current_target].radius;
+    camera.look_at_target(solar_system.get_body_position(current_target));
+    camera.set_distance(solar_system.camera_distance);
+
+    // O el estado de un snapshot
+    if let Some(snapshot) = snapshot {
+        camera.restore(&snapshot.camera);
+        current_target = snapshot.camera_target;
+        camera.target_radius = solar_system.bodies[current_target].radius;
+        clock = snapshot.clock;
+        total_time = snapshot.shader_time;
+    }
 
-    // Posición inicial de la cámara
-    camera.look_at_target(Vec3::zero());
-    camera.set_distance(35.0); // Increased to see Glacius (orbit 25.0) better
-
+    let help = control_help(&bindings);
     println!("Controles:");
-    println!("  W/S - Acercar/Alejar cámara");
-    println!("  A/D - Rotar cámara alrededor del objetivo");
-    println!("  1-6 - Cambiar a planeta (1=Sol, 2-6=Planetas)");
-    println!("  ESC - Salir");
-
-    while window.is_open() && !window.is_key_down(Key::Escape) {
-        // Delta time
+    for (keys, description) in &help {
+        println!("  {:<24} {}", keys, description);
+    }
+    println!(
+        "Snapshots ({} / {}): {}",
+        bindings.describe(Action::QuickSave),
+        bindings.describe(Action::QuickLoad),
+        quicksave.display()
+    );
+    let hint = format!(
+        "{} ayuda | {} ocultar HUD",
+        bindings.describe(Action::ToggleHelp),
+        bindings.describe(Action::ToggleHud)
+    );
+    let mut hud = Hud::new(help, hint);
+    hud.render_scale = target.render_scale();
+    hud.antialiasing = pipeline.antialiasing;
+
+    while window.is_open() && !bindings.is_down(&window, Action::Quit) {
+        // Delta time real
         let current_time = Instant::now();
-        let mut delta_time = current_time.duration_since(last_time).as_secs_f32();
+        let delta_time = current_time.duration_since(last_time).as_secs_f32();
         last_time = current_time;
 
-        // Clamp delta_time to prevent issues when window is paused/minimized
-        // Max 0.1 seconds (10 FPS minimum) to prevent huge jumps
-        delta_time = delta_time.min(0.1);
-        total_time += delta_time;
+        // Tiempo para animaciones de shaders (no depende de la simulación)
+        // Máximo 0.1 segundos para evitar saltos si la ventana se pausa
+        total_time += delta_time.min(0.1);
+        hud.record_frame(delta_time);
 
-        // === INPUT ===
-        handle_input(&window, &mut camera, &mut current_target, &solar_system);
-
-        // === UPDATE ===
-        solar_system.update(delta_time);
+        // El framebuffer sigue al tamaño de la ventana
+        let (width, height) = window.get_size();
+        if target.resize(width, height) {
+            camera.aspect = target.aspect();
+        }
 
-        // Actualizar objetivo de la cámara
-        let target_pos = solar_system.get_body_position(current_target);
-        camera.look_at_target(target_pos);
+        // === HUD ===
+        if bindings.is_pressed(&window, Action::ToggleHud) {
+            hud.visible = !hud.visible;
+        }
+        if bindings.is_pressed(&window, Action::ToggleHelp) {
+            hud.show_help = !hud.show_help;
+            hud.visible = true;
+        }
+        if bindings.is_pressed(&window, Action::ToggleLabels) {
+            pipeline.labels.enabled = !pipeline.labels.enabled;
+        }
+        let scale_up = bindings.is_pressed(&window, Action::RenderScaleUp);
+        if scale_up || bindings.is_pressed(&window, Action::RenderScaleDown) {
+            target.step_render_scale(scale_up);
+            hud.render_scale = target.render_scale();
+            let (width, height) = (target.scene.width, target.scene.height);
+            hud.notify(format!("Escala de render: {:.0}% ({}x{})", target.render_scale() * 100.0, width, height));
+        }
+        if bindings.is_pressed(&window, Action::ToggleAntialiasing) {
+            pipeline.antialiasing = pipeline.antialiasing.next();
+            hud.antialiasing = pipeline.antialiasing;
+            hud.notify(format!("Anti-aliasing: {}", pipeline.antialiasing.label()));
+        }
+        if bindings.is_pressed(&window, Action::CycleOrbitStyle) {
+            pipeline.orbit_back = pipeline.orbit_back.next();
+            hud.notify(format!("Órbitas detrás de su centro: {}", pipeline.orbit_back.label()));
+        }
 
-        // === RENDER ===
-        // Renderizar skybox primero (sin depth buffer)
-        render_skybox(&mut framebuffer, total_time);
-        
-        // Limpiar solo el depth buffer (mantener el skybox)
-        for depth in framebuffer.zbuffer.iter_mut() {
-            *depth = f32::INFINITY;
-        }
-
-        // Matriz VP (View-Projection)
-        let vp_matrix = camera.view_projection_matrix();
-
-        // Renderizar órbitas (primero, para que estén detrás)
-        render_orbits(&mut framebuffer, &solar_system, &vp_matrix);
-
-        // Renderizar cada cuerpo celeste
-        for body in &solar_system.bodies {
-            let model_matrix = body.get_model_matrix();
-            let mvp = vp_matrix.multiply(&model_matrix);
-
-            // Dirección de luz hacia este cuerpo (desde el sol)
-            let body_pos = body.get_position();
-            let light_dir = if body.is_emissive {
-                Vec3::zero() // El sol no necesita luz externa
-            } else {
-                (-body_pos).normalize() // Luz viene del centro (sol)
+        // === SNAPSHOTS ===
+        if bindings.is_pressed(&window, Action::QuickSave) {
+            let snapshot = Snapshot {
+                camera: camera.snapshot(),
+                camera_target: current_target,
+                clock: clock.clone(),
+                shader_time: total_time,
             };
-
-            // Renderizar cada triángulo
-            for triangle in &body.mesh {
-                // Transformar vértices a espacio de clip
-                let transformed = [
-                    transform_vertex_with_local(&triangle[0], &mvp, &model_matrix),
-                    transform_vertex_with_local(&triangle[1], &mvp, &model_matrix),
-                    transform_vertex_with_local(&triangle[2], &mvp, &model_matrix),
-                ];
-
-                // Back-face culling simple
-                let normal = calculate_face_normal(
-                    &transformed[0].0,
-                    &transformed[1].0,
-                    &transformed[2].0,
-                );
-                if normal.z > 0.0 {
-                    continue; // Cara trasera, no renderizar
+            match save_snapshot(&quicksave, &solar_system, &snapshot) {
+                Ok(()) => {
+                    println!("Snapshot guardado en {}", quicksave.display());
+                    hud.notify(format!("Snapshot guardado en {}", quicksave.display()));
                 }
-
-                // Frustum culling básico (si todos los vértices están fuera, saltar)
-                if !is_visible(&transformed[0].0)
-                    && !is_visible(&transformed[1].0)
-                    && !is_visible(&transformed[2].0)
-                {
-                    continue;
+                Err(err) => {
+                    eprintln!("No se pudo guardar el snapshot: {}", err);
+                    hud.notify("No se pudo guardar el snapshot");
                 }
-
-                // Rasterizar con shader
-                rasterize_with_shader(
-                    &mut framebuffer,
-                    &transformed,
-                    &[triangle[0].position, triangle[1].position, triangle[2].position],
-                    body.shader_type,
-                    total_time,
-                    &light_dir,
-                );
             }
         }
+        if bindings.is_pressed(&window, Action::QuickLoad) {
+            match load_snapshot(&quicksave) {
+                Ok((system, snapshot)) => {
+                    solar_system = system;
+                    camera.restore(&snapshot.camera);
+                    current_target = snapshot.camera_target;
+                    camera.target_radius = solar_system.bodies[current_target].radius;
+                    clock = snapshot.clock;
+                    total_time = snapshot.shader_time;
+                    println!("Snapshot cargado de {}", quicksave.display());
+                    hud.notify(format!("Snapshot cargado de {}", quicksave.display()));
+                }
+                Err(err) => {
+                    eprintln!("No se pudo cargar el snapshot: {}", err);
+                    hud.notify("No se pudo cargar el snapshot");
+                }
+            }
+        }
+
+        // === INPUT ===
+        let mouse_frame = mouse.update(&window);
+        handle_input(&window, &bindings, &mouse_frame, &mut camera, &mut current_target, &mut solar_system, &mut clock);
+        if camera.mode == CameraMode::FreeFlight {
+            // La nave se mueve en tiempo real, aunque la simulación esté en pausa
+            let input = flight_input(&window, &bindings, &mouse_frame);
+            camera.fly(&input, delta_time.min(0.1));
+        }
+
+        // === UPDATE ===
+        // Pasos fijos de simulación según la escala de tiempo
+        clock.advance(delta_time, |dt| solar_system.update(dt));
+
+        let mut status = format!("Space Travel - {} | {}", solar_system.name, clock.status());
+        if camera.mode == CameraMode::FreeFlight {
+            status.push_str(&format!(" | vuelo libre {:.1} u/s", camera.speed()));
+        }
+        if status != title {
+            window.set_title(&status);
+            title = status;
+        }
+
+        // Actualizar objetivo de la cámara (o el viaje hacia él)
+        if camera.is_warping() {
+            let target_pos = solar_system.get_body_position(current_target);
+            camera.update_warp(delta_time.min(0.1), target_pos);
+        } else if camera.mode == CameraMode::Orbit {
+            let target_pos = solar_system.get_body_position(current_target);
+            camera.look_at_target(target_pos);
+        }
+        // Nunca dentro de un cuerpo
+        camera.resolve_collisions(&solar_system.body_spheres(), delta_time.min(0.1));
+
+        // === RENDER ===
+        pipeline.render_frame(&mut target, &solar_system, &camera, total_time);
+        hud.draw(&mut target.output, &solar_system, &camera, current_target, &clock);
 
         // Mostrar en ventana
+        let (width, height) = target.size();
         window
-            .update_with_buffer(&framebuffer.buffer, WIDTH, HEIGHT)
+            .update_with_buffer(&target.output.buffer, width, height)
             .expect("Error al actualizar ventana");
     }
+
+    solar_system
+}
+
+/// Atajos de `--bindings`, del archivo por defecto si existe, o los de fábrica
+fn load_bindings(options: &Options) -> KeyBindings {
+    let default_path = Path::new(KeyBindings::DEFAULT_PATH);
+    let path = match &options.bindings {
+        Some(path) => path.as_path(),
+        None if default_path.exists() => default_path,
+        None => return KeyBindings::default(),
+    };
+    KeyBindings::load(path).unwrap_or_else(|err| {
+        eprintln!("No se pudieron cargar los atajos de {}: {}", path.display(), err);
+        std::process::exit(1);
+    })
 }
 
 /// Maneja el input del usuario
 fn handle_input(
     window: &Window,
+    bindings: &KeyBindings,
+    mouse: &MouseFrame,
     camera: &mut Camera,
     current_target: &mut usize,
-    solar_system: &SolarSystem,
+    solar_system: &mut SolarSystem,
+    clock: &mut SimulationClock,
 ) {
-    // Zoom
-    if window.is_key_down(Key::W) {
-        camera.set_distance(camera.distance_from_target - 0.5);
+    // Alternar vuelo libre / seguir objetivo
+    if bindings.is_pressed(window, Action::ToggleFreeFlight) {
+        camera.toggle_free_flight();
+        match camera.mode {
+            CameraMode::FreeFlight => println!("Vuelo libre"),
+            CameraMode::Orbit => println!("Siguiendo a: {}", solar_system.bodies[*current_target].name),
+        }
     }
-    if window.is_key_down(Key::S) {
-        camera.set_distance(camera.distance_from_target + 0.5);
+
+    // En vuelo libre las mismas teclas pueden controlar la nave (ver `flight_input`)
+    if camera.mode == CameraMode::Orbit && !camera.is_warping() {
+        // Zoom
+        if bindings.is_down(window, Action::ZoomIn) {
+            camera.set_distance(camera.distance_from_target - 0.5);
+        }
+        if bindings.is_down(window, Action::ZoomOut) {
+            camera.set_distance(camera.distance_from_target + 0.5);
+        }
+
+        // Rotación
+        if bindings.is_down(window, Action::RotateLeft) {
+            camera.rotate(-0.03);
+        }
+        if bindings.is_down(window, Action::RotateRight) {
+            camera.rotate(0.03);
+        }
+
+        // Mouse: arrastrar gira alrededor del objetivo, la rueda acerca/aleja
+        if mouse.drag != (0.0, 0.0) {
+            camera.orbit(-mouse.drag.0 * 0.01, mouse.drag.1 * 0.01);
+        }
+        if mouse.scroll != 0.0 {
+            camera.zoom(0.9_f32.powf(mouse.scroll.clamp(-3.0, 3.0)));
+        }
+    }
+
+    // Clic sobre un cuerpo: viajar a él
+    if let Some((x, y)) = mouse.click {
+        let spheres = solar_system.body_spheres();
+        let (width, height) = mouse.screen;
+        if let Some(i) = camera.pick(x + 0.5, y + 0.5, width, height, &spheres) {
+            select_target(camera, current_target, solar_system, i);
+        }
     }
 
-    // Rotación
-    if window.is_key_down(Key::A) {
-        camera.rotate(-0.03);
+    // Control del tiempo de simulación
+    if bindings.is_pressed(window, Action::Pause) {
+        clock.toggle_pause();
     }
-    if window.is_key_down(Key::D) {
-        camera.rotate(0.03);
+    if bindings.is_pressed(window, Action::Reverse) {
+        clock.toggle_reverse();
+    }
+    if bindings.is_pressed(window, Action::Faster) {
+        clock.faster();
+    }
+    if bindings.is_pressed(window, Action::Slower) {
+        clock.slower();
+    }
+    if bindings.is_pressed(window, Action::NormalSpeed) {
+        clock.set_time_scale(1.0);
     }
 
-    // Cambiar objetivo
-    let keys = [Key::Key1, Key::Key2, Key::Key3, Key::Key4, Key::Key5, Key::Key6];
-    for (i, key) in keys.iter().enumerate() {
-        if window.is_key_pressed(*key, minifb::KeyRepeat::No) {
-            if i < solar_system.body_count() {
-                *current_target = i;
-                // Ajustar distancia según el tamaño del planeta
-                let body = solar_system.get_body(i).unwrap();
-                camera.set_distance(body.radius * 8.0 + 5.0);
-                println!("Siguiendo a: {}", body.name);
-            }
+    // Alternar gravedad N-cuerpos / órbitas fijas
+    if bindings.is_pressed(window, Action::TogglePhysics) {
+        let enabled = !solar_system.physics_enabled();
+        solar_system.set_physics(enabled);
+        println!("Gravedad: {}", if enabled { "activada" } else { "desactivada" });
+    }
+
+    // Cambiar objetivo: recorrer todos los cuerpos o elegir uno de los primeros nueve
+    let count = solar_system.body_count();
+    if bindings.is_pressed(window, Action::NextTarget) {
+        select_target(camera, current_target, solar_system, (*current_target + 1) % count);
+    }
+    if bindings.is_pressed(window, Action::PrevTarget) {
+        select_target(camera, current_target, solar_system, (*current_target + count - 1) % count);
+    }
+    for (i, action) in Action::TARGETS.into_iter().enumerate() {
+        if bindings.is_pressed(window, action) && i < count {
+            select_target(camera, current_target, solar_system, i);
         }
     }
 }
 
-/// Transforma un vértice con las matrices MVP
-fn transform_vertex(
-    v: &renderer::Vertex,
-    mvp: &Mat4,
-    model: &Mat4,
-) -> renderer::Vertex {
-    let transformed_pos = mvp.transform_point(&v.position);
-    let transformed_normal = model.transform_direction(&v.normal).normalize();
-    
-    renderer::Vertex::new(transformed_pos, transformed_normal, v.color)
+/// Cambia de objetivo viajando hasta él
+fn select_target(camera: &mut Camera, current_target: &mut usize, solar_system: &SolarSystem, index: usize) {
+    *current_target = index;
+    // La distancia depende del tamaño del planeta
+    let body = &solar_system.bodies[index];
+    camera.warp_to(
+        index,
+        solar_system.get_body_position(index),
+        body.radius,
+        body.radius * 8.0 + 5.0,
+        &solar_system.body_spheres(),
+    );
+    println!("Viajando a: {}", body.name);
 }
 
-/// Transforma un vértice y retorna (clip_pos, world_normal, local_pos)
-fn transform_vertex_with_local(
-    v: &renderer::Vertex,
-    mvp: &Mat4,
-    model: &Mat4,
-) -> (Vec3, Vec3, Vec3) {
-    let clip_pos = mvp.transform_point(&v.position);
-    let world_normal = model.transform_direction(&v.normal).normalize();
-    (clip_pos, world_normal, v.position)
+/// Eventos del mouse en un frame
+#[derive(Debug, Default)]
+struct MouseFrame {
+    drag: (f32, f32),          // Desplazamiento arrastrando con el botón izquierdo
+    click: Option<(f32, f32)>, // Posición de un clic (apretar y soltar sin arrastrar)
+    scroll: f32,               // Rueda (positivo = hacia adelante)
+    screen: (usize, usize),    // Tamaño de la ventana al que se refieren las posiciones
 }
 
-/// Rasteriza un triángulo aplicando shaders por fragmento
-fn rasterize_with_shader(
-    fb: &mut Framebuffer,
-    transformed: &[(Vec3, Vec3, Vec3); 3], // (clip_pos, world_normal, local_pos)
-    local_positions: &[Vec3; 3],
-    shader_type: ShaderType,
-    time: f32,
-    light_dir: &Vec3,
-) {
-    // Convertir a coordenadas de pantalla
-    let screen = [
-        to_screen(transformed[0].0, fb.width, fb.height),
-        to_screen(transformed[1].0, fb.width, fb.height),
-        to_screen(transformed[2].0, fb.width, fb.height),
-    ];
-
-    // Bounding box
-    let min_x = screen[0].0.min(screen[1].0).min(screen[2].0).max(0) as usize;
-    let max_x = screen[0].0.max(screen[1].0).max(screen[2].0).min(fb.width as i32 - 1) as usize;
-    let min_y = screen[0].1.min(screen[1].1).min(screen[2].1).max(0) as usize;
-    let max_y = screen[0].1.max(screen[1].1).max(screen[2].1).min(fb.height as i32 - 1) as usize;
-
-    // Área del triángulo
-    let area = edge_function(screen[0], screen[1], screen[2]);
-    if area.abs() < 0.001 {
-        return;
-    }
+/// Estado del mouse entre frames, para distinguir arrastre de clic
+#[derive(Debug, Default)]
+struct MouseTracker {
+    last: Option<(f32, f32)>, // Posición anterior mientras el botón está apretado
+    travel: f32,              // Píxeles recorridos desde que se apretó
+}
 
-    for y in min_y..=max_y {
-        for x in min_x..=max_x {
-            let p = (x as i32, y as i32);
-
-            // Coordenadas baricéntricas
-            let w0 = edge_function(screen[1], screen[2], p);
-            let w1 = edge_function(screen[2], screen[0], p);
-            let w2 = edge_function(screen[0], screen[1], p);
-
-            // Verificar si está dentro del triángulo
-            if (w0 >= 0.0 && w1 >= 0.0 && w2 >= 0.0) || (w0 <= 0.0 && w1 <= 0.0 && w2 <= 0.0) {
-                let w0 = w0 / area;
-                let w1 = w1 / area;
-                let w2 = w2 / area;
-
-                // Interpolar Z
-                let z = transformed[0].0.z * w0 + transformed[1].0.z * w1 + transformed[2].0.z * w2;
-
-                // Interpolar normal
-                let normal = Vec3::new(
-                    transformed[0].1.x * w0 + transformed[1].1.x * w1 + transformed[2].1.x * w2,
-                    transformed[0].1.y * w0 + transformed[1].1.y * w1 + transformed[2].1.y * w2,
-                    transformed[0].1.z * w0 + transformed[1].1.z * w1 + transformed[2].1.z * w2,
-                ).normalize();
-
-                // Interpolar posición local (para el shader)
-                let local_pos = Vec3::new(
-                    local_positions[0].x * w0 + local_positions[1].x * w1 + local_positions[2].x * w2,
-                    local_positions[0].y * w0 + local_positions[1].y * w1 + local_positions[2].y * w2,
-                    local_positions[0].z * w0 + local_positions[1].z * w1 + local_positions[2].z * w2,
-                );
-
-                // Normalizar posición para shaders (esperan posiciones en esfera unitaria)
-                let normalized_pos = local_pos.normalize();
-
-                // Crear datos del fragmento
-                let fragment = FragmentData {
-                    position: normalized_pos,
-                    normal,
-                    world_pos: local_pos, // Simplificado
-                };
-
-                // Aplicar shader
-                let color = apply_shader(shader_type, &fragment, time, light_dir);
-                let color_u32 = rgb_to_u32(
-                    (color.0.clamp(0.0, 1.0) * 255.0) as u8,
-                    (color.1.clamp(0.0, 1.0) * 255.0) as u8,
-                    (color.2.clamp(0.0, 1.0) * 255.0) as u8,
-                );
-
-                fb.set_pixel(x, y, z, color_u32);
+impl MouseTracker {
+    /// Recorrido máximo (píxeles) para que soltar el botón cuente como clic
+    const CLICK_TRAVEL: f32 = 4.0;
+
+    fn update(&mut self, window: &Window) -> MouseFrame {
+        let mut frame = MouseFrame {
+            scroll: window.get_scroll_wheel().map_or(0.0, |(_, y)| y),
+            screen: window.get_size(),
+            ..MouseFrame::default()
+        };
+
+        if window.get_mouse_down(MouseButton::Left) {
+            if let Some((x, y)) = window.get_mouse_pos(MouseMode::Pass) {
+                match self.last {
+                    Some((last_x, last_y)) => {
+                        frame.drag = (x - last_x, y - last_y);
+                        self.travel += frame.drag.0.abs() + frame.drag.1.abs();
+                    }
+                    None => self.travel = 0.0,
+                }
+                self.last = Some((x, y));
+            }
+        } else if let Some(position) = self.last.take() {
+            if self.travel < Self::CLICK_TRAVEL {
+                frame.click = Some(position);
             }
         }
+        frame
     }
 }
 
-fn to_screen(pos: Vec3, width: usize, height: usize) -> (i32, i32) {
-    let x = ((pos.x + 1.0) * 0.5 * width as f32) as i32;
-    let y = ((1.0 - pos.y) * 0.5 * height as f32) as i32;
-    (x, y)
+/// Lee los controles de vuelo libre de este frame
+///
+/// Arrastrar con el botón izquierdo mira según el desplazamiento del mouse.
+fn flight_input(window: &Window, bindings: &KeyBindings, mouse: &MouseFrame) -> FlightInput {
+    let axis = |positive, negative| bindings.axis(window, positive, negative);
+
+    FlightInput {
+        thrust: Vec3::new(
+            axis(Action::StrafeRight, Action::StrafeLeft),
+            axis(Action::ThrustUp, Action::ThrustDown),
+            axis(Action::ThrustForward, Action::ThrustBack),
+        ),
+        yaw: axis(Action::LookRight, Action::LookLeft),
+        pitch: axis(Action::LookUp, Action::LookDown),
+        roll: axis(Action::RollRight, Action::RollLeft),
+        look: mouse.drag,
+        boost: bindings.is_down(window, Action::Boost),
+        brake: bindings.is_down(window, Action::Brake),
+    }
 }
 
-fn edge_function(v0: (i32, i32), v1: (i32, i32), p: (i32, i32)) -> f32 {
-    ((p.0 - v0.0) * (v1.1 - v0.1) - (p.1 - v0.1) * (v1.0 - v0.0)) as f32
+/// Filas de la ayuda de controles: los atajos cargados y el mouse
+fn control_help(bindings: &KeyBindings) -> Vec<(String, String)> {
+    let mut help = bindings.help();
+    help.push(("Arrastrar".to_string(), "Girar alrededor del objetivo (o mirar en vuelo libre)".to_string()));
+    help.push(("Rueda".to_string(), "Acercar / alejar".to_string()));
+    help.push(("Clic".to_string(), "Viajar al cuerpo bajo el cursor".to_string()));
+    help
 }
 
-/// Calcula la normal de una cara (para back-face culling)
-fn calculate_face_normal(v0: &Vec3, v1: &Vec3, v2: &Vec3) -> Vec3 {
-    let edge1 = *v1 - *v0;
-    let edge2 = *v2 - *v0;
-    edge1.cross(&edge2).normalize()
+/// Opciones de línea de comandos
+struct Options {
+    scene: Option<PathBuf>,      // Archivo de escena a cargar
+    snapshot: Option<PathBuf>,   // Snapshot inicial (y destino de F5/F9)
+    save_scene: Option<PathBuf>, // Guardar el sistema al terminar
+    headless: bool,              // Render offscreen a archivos
+    physics: bool,               // Forzar la gravedad N-cuerpos
+    skybox: Option<PathBuf>,     // Imagen equirectangular o directorio de cubemap
+    warp: Option<f32>,           // Duración de los viajes entre objetivos
+    render_scale: f32,           // Resolución interna respecto a la de salida
+    antialiasing: Antialiasing,  // MSAA de los cuerpos
+    orbit_back: OrbitBackStyle,  // Tramo de las órbitas detrás de su centro
+    bindings: Option<PathBuf>,   // Archivo de atajos de teclado
+    output_dir: PathBuf,
+    frames: usize,
+    timestep: f32,
+    format: ImageFormat,
+    width: usize,
+    height: usize,
+    target: Option<usize>,       // Por defecto, el objetivo de la escena
+    distance: Option<f32>,       // Por defecto, la distancia de la escena
 }
 
-/// Verifica si un punto está en el frustum visible
-fn is_visible(p: &Vec3) -> bool {
-    p.x >= -1.5 && p.x <= 1.5 && p.y >= -1.5 && p.y <= 1.5 && p.z >= 0.0 && p.z <= 1.0
+impl Options {
+    const USAGE: &'static str = "Uso: solar2 [--scene ARCHIVO | --snapshot ARCHIVO] [--save-scene ARCHIVO] [--physics] [--skybox IMAGEN.ppm|DIR] [--warp SEGUNDOS] [--bindings ARCHIVO]\n\
+\x20      [--width W] [--height H] [--render-scale S] [--aa none|msaa2|msaa4] [--orbit-back solid|dashed|faded]\n\
+\x20      solar2 --headless [--scene ARCHIVO | --snapshot ARCHIVO] [--save-scene ARCHIVO] [--physics] [--skybox IMAGEN.ppm|DIR] [--output DIR] [--frames N] \
+[--dt SEGUNDOS] [--format png|ppm] [--width W] [--height H] [--render-scale S] [--aa none|msaa2|msaa4]\n\
+\x20      [--orbit-back solid|dashed|faded] [--target INDICE] [--distance D]";
+
+    /// Interpreta los argumentos de línea de comandos
+    fn parse(args: &[String]) -> Result<Self, String> {
+        let mut options = Self {
+            scene: None,
+            snapshot: None,
+            save_scene: None,
+            headless: false,
+            physics: false,
+            skybox: None,
+            warp: None,
+            render_scale: 1.0,
+            antialiasing: Antialiasing::None,
+            orbit_back: OrbitBackStyle::Solid,
+            bindings: None,
+            output_dir: PathBuf::from("frames"),
+            frames: 60,
+            timestep: 1.0 / 60.0,
+            format: ImageFormat::Png,
+            width: WIDTH,
+            height: HEIGHT,
+            target: None,
+            distance: None,
+        };
+
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            if arg == "--headless" {
+                options.headless = true;
+                continue;
+            }
+            if arg == "--physics" {
+                options.physics = true;
+                continue;
+            }
+            let value = iter
+                .next()
+                .ok_or_else(|| format!("falta el valor para {}", arg))?;
+            match arg.as_str() {
+                "--scene" => options.scene = Some(PathBuf::from(value)),
+                "--snapshot" => options.snapshot = Some(PathBuf::from(value)),
+                "--save-scene" => options.save_scene = Some(PathBuf::from(value)),
+                "--skybox" => options.skybox = Some(PathBuf::from(value)),
+                "--warp" => options.warp = Some(parse_number(arg, value)?),
+                "--bindings" => options.bindings = Some(PathBuf::from(value)),
+                "--render-scale" => options.render_scale = parse_number(arg, value)?,
+                "--aa" => {
+                    options.antialiasing = Antialiasing::from_name(value)
+                        .ok_or_else(|| format!("anti-aliasing desconocido: {}", value))?;
+                }
+                "--orbit-back" => {
+                    options.orbit_back = OrbitBackStyle::from_name(value)
+                        .ok_or_else(|| format!("estilo de órbita desconocido: {}", value))?;
+                }
+                "--output" => options.output_dir = PathBuf::from(value),
+                "--frames" => options.frames = parse_number(arg, value)?,
+                "--dt" => options.timestep = parse_number(arg, value)?,
+                "--format" => {
+                    options.format = ImageFormat::from_name(value)
+                        .ok_or_else(|| format!("formato desconocido: {}", value))?;
+                }
+                "--width" => options.width = parse_number(arg, value)?,
+                "--height" => options.height = parse_number(arg, value)?,
+                "--target" => options.target = Some(parse_number(arg, value)?),
+                "--distance" => options.distance = Some(parse_number(arg, value)?),
+                _ => return Err(format!("argumento desconocido: {}", arg)),
+            }
+        }
+
+        if options.width == 0 || options.height == 0 {
+            return Err("la resolución debe ser mayor que cero".to_string());
+        }
+        if !(RenderTarget::MIN_SCALE..=RenderTarget::MAX_SCALE).contains(&options.render_scale) {
+            return Err(format!(
+                "--render-scale debe estar entre {} y {}",
+                RenderTarget::MIN_SCALE,
+                RenderTarget::MAX_SCALE
+            ));
+        }
+        if options.warp.is_some_and(|warp| !warp.is_finite() || warp < 0.0) {
+            return Err("--warp no puede ser negativo".to_string());
+        }
+        // NaN no cumple ninguna comparación: se rechaza aparte
+        if !options.timestep.is_finite() || options.timestep <= 0.0 {
+            return Err("--dt debe ser positivo".to_string());
+        }
+        Ok(options)
+    }
 }
 
-/// Renderiza las órbitas de los planetas
-fn render_orbits(fb: &mut Framebuffer, solar_system: &SolarSystem, vp_matrix: &Mat4) {
-    for (i, orbit) in solar_system.orbit_points.iter().enumerate() {
-        if orbit.is_empty() {
-            continue;
-        }
-
-        // Color de órbita basado en el planeta
-        let body = &solar_system.bodies[i];
-        let orbit_color = rgb_to_u32(
-            (body.color.0 * 100.0) as u8,
-            (body.color.1 * 100.0) as u8,
-            (body.color.2 * 100.0) as u8,
-        );
-
-        for j in 0..orbit.len() {
-            let p1 = &orbit[j];
-            let p2 = &orbit[(j + 1) % orbit.len()];
-
-            // Transformar puntos
-            let sp1 = vp_matrix.transform_point(p1);
-            let sp2 = vp_matrix.transform_point(p2);
-
-            // Convertir a coordenadas de pantalla
-            if sp1.z > 0.0 && sp2.z > 0.0 && sp1.z < 1.0 && sp2.z < 1.0 {
-                let x1 = ((sp1.x + 1.0) * 0.5 * fb.width as f32) as i32;
-                let y1 = ((1.0 - sp1.y) * 0.5 * fb.height as f32) as i32;
-                let x2 = ((sp2.x + 1.0) * 0.5 * fb.width as f32) as i32;
-                let y2 = ((1.0 - sp2.y) * 0.5 * fb.height as f32) as i32;
-
-                draw_line(fb, x1, y1, x2, y2, orbit_color);
-            }
+fn parse_number<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("valor inválido para {}: {}", flag, value))
+}
+
+/// Simula con un timestep fijo y guarda cada frame en disco, sin abrir ventana
+///
+/// Cada frame equivale a `--dt` segundos reales. Sin snapshot se simula un
+/// paso de `--dt` por frame; con snapshot manda su reloj (paso, escala, pausa
+/// y reversa) y la cámara conserva su modo.
+fn run_headless(options: &Options, pipeline: &Pipeline, mut solar_system: SolarSystem, snapshot: Option<Snapshot>) -> SolarSystem {
+    let mut render_target = RenderTarget::new(options.width, options.height, options.render_scale);
+    let mut camera = Camera::new(render_target.aspect());
+    let mut total_time: f32 = 0.0;
+    let mut target = solar_system.camera_target;
+    let mut clock = SimulationClock::new(options.timestep);
+
+    camera.look_at_target(solar_system.get_body_position(target));
+    camera.set_distance(solar_system.camera_distance);
+
+    // El snapshot define la cámara, el reloj y el tiempo de los shaders
+    if let Some(snapshot) = snapshot {
+        camera.restore(&snapshot.camera);
+        target = snapshot.camera_target;
+        total_time = snapshot.shader_time;
+        clock = snapshot.clock;
+    }
+    // Un frame nunca se recorta: `--dt` puede superar el límite de la ventana
+    clock.max_frame_time = clock.max_frame_time.max(options.timestep);
+
+    // Las opciones explícitas tienen prioridad
+    let target = options.target.unwrap_or(target).min(solar_system.body_count() - 1);
+    camera.target_radius = solar_system.bodies[target].radius;
+    if let Some(distance) = options.distance {
+        camera.set_distance(distance);
+    }
+
+    for frame in 0..options.frames {
+        total_time += options.timestep;
+        clock.advance(options.timestep, |dt| solar_system.update(dt));
+        if camera.mode == CameraMode::Orbit {
+            camera.look_at_target(solar_system.get_body_position(target));
+        }
+        camera.resolve_collisions(&solar_system.body_spheres(), options.timestep);
+
+        pipeline.render_frame(&mut render_target, &solar_system, &camera, total_time);
+
+        let file_name = format!("frame_{:05}.{}", frame, options.format.extension());
+        let path = options.output_dir.join(file_name);
+        if let Err(err) = save_frame(&render_target.output, &path, options.format) {
+            eprintln!("No se pudo guardar {}: {}", path.display(), err);
+            std::process::exit(1);
         }
     }
-}
\ No newline at end of file
+
+    println!(
+        "{} frames guardados en {}",
+        options.frames,
+        options.output_dir.display()
+    );
+    solar_system
+}
@@ -1,4 +1,5 @@
 use super::vec3::Vec3;
+use super::vec4::Vec4;
 
 #[derive(Debug, Clone, Copy)]
 pub struct Mat4 {
@@ -146,6 +147,22 @@ impl Mat4 {
         }
     }
 
+    /// Transformar un vector homogéneo (sin división de perspectiva)
+    pub fn transform_vec4(&self, v: &Vec4) -> Vec4 {
+        let d = &self.data;
+        Vec4::new(
+            d[0][0] * v.x + d[0][1] * v.y + d[0][2] * v.z + d[0][3] * v.w,
+            d[1][0] * v.x + d[1][1] * v.y + d[1][2] * v.z + d[1][3] * v.w,
+            d[2][0] * v.x + d[2][1] * v.y + d[2][2] * v.z + d[2][3] * v.w,
+            d[3][0] * v.x + d[3][1] * v.y + d[3][2] * v.z + d[3][3] * v.w,
+        )
+    }
+
+    /// Transformar un punto a espacio de clip (w se conserva para recortar)
+    pub fn transform_point_clip(&self, p: &Vec3) -> Vec4 {
+        self.transform_vec4(&Vec4::from_point(*p))
+    }
+
     /// Transformar dirección (sin traslación)
     pub fn transform_direction(&self, d: &Vec3) -> Vec3 {
         Vec3::new(
@@ -154,4 +171,44 @@ impl Mat4 {
             self.data[2][0] * d.x + self.data[2][1] * d.y + self.data[2][2] * d.z,
         )
     }
-}
\ No newline at end of file
+
+    /// Matriz inversa (eliminación de Gauss-Jordan con pivoteo parcial)
+    ///
+    /// Devuelve `None` si la matriz es singular.
+    pub fn inverse(&self) -> Option<Mat4> {
+        let mut a = self.data;
+        let mut inv = Mat4::identity().data;
+
+        for col in 0..4 {
+            // Fila con el pivote de mayor magnitud
+            let pivot = (col..4)
+                .max_by(|&i, &j| a[i][col].abs().total_cmp(&a[j][col].abs()))
+                .unwrap_or(col);
+            if a[pivot][col].abs() < f32::EPSILON * 1e-3 {
+                return None;
+            }
+            a.swap(col, pivot);
+            inv.swap(col, pivot);
+
+            let scale = 1.0 / a[col][col];
+            for j in 0..4 {
+                a[col][j] *= scale;
+                inv[col][j] *= scale;
+            }
+
+            for row in 0..4 {
+                if row == col {
+                    continue;
+                }
+                let factor = a[row][col];
+                if factor != 0.0 {
+                    for j in 0..4 {
+                        a[row][j] -= factor * a[col][j];
+                        inv[row][j] -= factor * inv[col][j];
+                    }
+                }
+            }
+        }
+        Some(Mat4 { data: inv })
+    }
+}
@@ -0,0 +1,101 @@
+use std::ops::Mul;
+use super::vec3::Vec3;
+use super::mat4::Mat4;
+
+/// Cuaternión unitario para representar orientaciones sin gimbal lock
+#[derive(Debug, Clone, Copy)]
+pub struct Quat {
+    pub w: f32,
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+}
+
+impl Quat {
+    pub fn new(w: f32, x: f32, y: f32, z: f32) -> Self {
+        Self { w, x, y, z }
+    }
+
+    pub fn identity() -> Self {
+        Self::new(1.0, 0.0, 0.0, 0.0)
+    }
+
+    /// Rotación de `angle` radianes alrededor de `axis`
+    pub fn from_axis_angle(axis: Vec3, angle: f32) -> Self {
+        let axis = axis.normalize();
+        let (s, c) = (angle * 0.5).sin_cos();
+        Self::new(c, axis.x * s, axis.y * s, axis.z * s)
+    }
+
+    /// Orientación que mira hacia `forward` con el eje Y como arriba
+    ///
+    /// En espacio local se mira hacia -Z, como la cámara.
+    pub fn look_rotation(forward: Vec3) -> Self {
+        let f = forward.normalize();
+        let yaw = (-f.x).atan2(-f.z);
+        let pitch = f.y.clamp(-1.0, 1.0).asin();
+        Self::from_axis_angle(Vec3::new(0.0, 1.0, 0.0), yaw)
+            * Self::from_axis_angle(Vec3::new(1.0, 0.0, 0.0), pitch)
+    }
+
+    pub fn length(&self) -> f32 {
+        (self.w * self.w + self.x * self.x + self.y * self.y + self.z * self.z).sqrt()
+    }
+
+    /// Renormaliza (corrige la deriva numérica tras muchas multiplicaciones)
+    pub fn normalize(&self) -> Quat {
+        let len = self.length();
+        if len > 0.0 {
+            Quat::new(self.w / len, self.x / len, self.y / len, self.z / len)
+        } else {
+            Quat::identity()
+        }
+    }
+
+    pub fn conjugate(&self) -> Quat {
+        Quat::new(self.w, -self.x, -self.y, -self.z)
+    }
+
+    /// Rota un vector
+    pub fn rotate(&self, v: Vec3) -> Vec3 {
+        // v' = v + 2w(q × v) + 2 q × (q × v)
+        let q = Vec3::new(self.x, self.y, self.z);
+        let t = q.cross(&v) * 2.0;
+        v + t * self.w + q.cross(&t)
+    }
+
+    /// Matriz de rotación equivalente
+    pub fn to_mat4(&self) -> Mat4 {
+        let (w, x, y, z) = (self.w, self.x, self.y, self.z);
+        let mut m = Mat4::identity();
+        m.data[0][0] = 1.0 - 2.0 * (y * y + z * z);
+        m.data[0][1] = 2.0 * (x * y - w * z);
+        m.data[0][2] = 2.0 * (x * z + w * y);
+        m.data[1][0] = 2.0 * (x * y + w * z);
+        m.data[1][1] = 1.0 - 2.0 * (x * x + z * z);
+        m.data[1][2] = 2.0 * (y * z - w * x);
+        m.data[2][0] = 2.0 * (x * z - w * y);
+        m.data[2][1] = 2.0 * (y * z + w * x);
+        m.data[2][2] = 1.0 - 2.0 * (x * x + y * y);
+        m
+    }
+}
+
+impl Default for Quat {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+/// Composición de rotaciones: `a * b` aplica primero `b` y luego `a`
+impl Mul for Quat {
+    type Output = Quat;
+    fn mul(self, o: Quat) -> Quat {
+        Quat::new(
+            self.w * o.w - self.x * o.x - self.y * o.y - self.z * o.z,
+            self.w * o.x + self.x * o.w + self.y * o.z - self.z * o.y,
+            self.w * o.y - self.x * o.z + self.y * o.w + self.z * o.x,
+            self.w * o.z + self.x * o.y - self.y * o.x + self.z * o.w,
+        )
+    }
+}
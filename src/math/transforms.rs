@@ -22,23 +22,31 @@ pub fn create_model_matrix(
 }
 
 /// Proyecta un punto 3D a coordenadas de pantalla 2D
+///
+/// Devuelve (x, y, z) con z en NDC (-1 = plano cercano, 1 = lejano), el mismo
+/// valor que guarda el z-buffer. `None` si el punto está detrás de la cámara o
+/// fuera del rango de profundidad; x e y pueden caer fuera de la pantalla.
 pub fn project_to_screen(
     point: Vec3,
     mvp: &Mat4,
     screen_width: usize,
     screen_height: usize,
 ) -> Option<(i32, i32, f32)> {
-    let transformed = mvp.transform_point(&point);
-    
-    // Verificar si está dentro del frustum
-    if transformed.z < 0.0 || transformed.z > 1.0 {
+    let clip = mvp.transform_point_clip(&point);
+
+    // Detrás de la cámara: la división por w invertiría el punto
+    if clip.w <= 0.0 {
+        return None;
+    }
+    let ndc = clip.perspective_divide();
+    if ndc.z < -1.0 || ndc.z > 1.0 {
         return None;
     }
-    
-    let x = ((transformed.x + 1.0) * 0.5 * screen_width as f32) as i32;
-    let y = ((1.0 - transformed.y) * 0.5 * screen_height as f32) as i32;
-    
-    Some((x, y, transformed.z))
+
+    let x = ((ndc.x + 1.0) * 0.5 * screen_width as f32) as i32;
+    let y = ((1.0 - ndc.y) * 0.5 * screen_height as f32) as i32;
+
+    Some((x, y, ndc.z))
 }
 
 /// Interpola linealmente entre dos valores
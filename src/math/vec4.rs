@@ -0,0 +1,62 @@
+use std::ops::{Add, Sub, Mul};
+use super::vec3::Vec3;
+
+/// Vector homogéneo (x, y, z, w), usado en espacio de clip
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Vec4 {
+    pub x: f32,
+    pub y: f32,
+    pub z: f32,
+    pub w: f32,
+}
+
+impl Vec4 {
+    pub fn new(x: f32, y: f32, z: f32, w: f32) -> Self {
+        Self { x, y, z, w }
+    }
+
+    /// Punto 3D en coordenadas homogéneas (w = 1)
+    pub fn from_point(p: Vec3) -> Self {
+        Self::new(p.x, p.y, p.z, 1.0)
+    }
+
+    /// Componentes xyz sin dividir
+    pub fn xyz(&self) -> Vec3 {
+        Vec3::new(self.x, self.y, self.z)
+    }
+
+    /// División de perspectiva (a coordenadas normalizadas NDC)
+    pub fn perspective_divide(&self) -> Vec3 {
+        if self.w != 0.0 {
+            Vec3::new(self.x / self.w, self.y / self.w, self.z / self.w)
+        } else {
+            self.xyz()
+        }
+    }
+
+    /// Interpolación lineal entre dos vectores
+    pub fn lerp(a: &Vec4, b: &Vec4, t: f32) -> Vec4 {
+        *a + (*b - *a) * t
+    }
+}
+
+impl Add for Vec4 {
+    type Output = Vec4;
+    fn add(self, other: Vec4) -> Vec4 {
+        Vec4::new(self.x + other.x, self.y + other.y, self.z + other.z, self.w + other.w)
+    }
+}
+
+impl Sub for Vec4 {
+    type Output = Vec4;
+    fn sub(self, other: Vec4) -> Vec4 {
+        Vec4::new(self.x - other.x, self.y - other.y, self.z - other.z, self.w - other.w)
+    }
+}
+
+impl Mul<f32> for Vec4 {
+    type Output = Vec4;
+    fn mul(self, s: f32) -> Vec4 {
+        Vec4::new(self.x * s, self.y * s, self.z * s, self.w * s)
+    }
+}
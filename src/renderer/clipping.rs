@@ -0,0 +1,228 @@
+use crate::math::{Vec3, Vec4};
+
+/// Vértice en espacio de clip con los atributos que se interpolan al recortar
+#[derive(Debug, Clone, Copy)]
+pub struct ClipVertex {
+    pub position: Vec4, // Posición en espacio de clip (antes de dividir por w)
+    pub normal: Vec3,   // Normal en espacio mundo
+    pub world: Vec3,    // Posición en espacio mundo
+    pub local: Vec3,    // Posición en espacio local (para el shader)
+}
+
+impl ClipVertex {
+    /// Interpola todos los atributos (lineal en espacio de clip)
+    pub fn lerp(a: &ClipVertex, b: &ClipVertex, t: f32) -> ClipVertex {
+        ClipVertex {
+            position: Vec4::lerp(&a.position, &b.position, t),
+            normal: a.normal + (b.normal - a.normal) * t,
+            world: a.world + (b.world - a.world) * t,
+            local: a.local + (b.local - a.local) * t,
+        }
+    }
+}
+
+/// Planos del frustum en espacio de clip (-w <= x, y, z <= w)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ClipPlane {
+    Near,
+    Far,
+    Left,
+    Right,
+    Bottom,
+    Top,
+}
+
+impl ClipPlane {
+    pub const ALL: [ClipPlane; 6] = [
+        ClipPlane::Near,
+        ClipPlane::Far,
+        ClipPlane::Left,
+        ClipPlane::Right,
+        ClipPlane::Bottom,
+        ClipPlane::Top,
+    ];
+
+    /// Distancia con signo al plano (>= 0 significa dentro)
+    pub fn distance(&self, p: &Vec4) -> f32 {
+        match self {
+            ClipPlane::Near => p.z + p.w,
+            ClipPlane::Far => p.w - p.z,
+            ClipPlane::Left => p.x + p.w,
+            ClipPlane::Right => p.w - p.x,
+            ClipPlane::Bottom => p.y + p.w,
+            ClipPlane::Top => p.w - p.y,
+        }
+    }
+
+    fn bit(&self) -> u8 {
+        1 << (*self as u8)
+    }
+}
+
+/// Bits de los planos que dejan fuera al punto
+fn outcode(p: &Vec4) -> u8 {
+    ClipPlane::ALL
+        .iter()
+        .filter(|plane| plane.distance(p) < 0.0)
+        .fold(0, |code, plane| code | plane.bit())
+}
+
+/// Recorta un triángulo contra el frustum (Sutherland–Hodgman)
+///
+/// Agrega a `out` los triángulos resultantes (abanico del polígono recortado).
+/// Los triángulos completamente dentro pasan sin cambios y los completamente
+/// fuera de algún plano se descartan.
+pub fn clip_triangle(triangle: &[ClipVertex; 3], out: &mut Vec<[ClipVertex; 3]>) {
+    let codes = [
+        outcode(&triangle[0].position),
+        outcode(&triangle[1].position),
+        outcode(&triangle[2].position),
+    ];
+
+    // Todo dentro: aceptar directamente
+    if codes[0] | codes[1] | codes[2] == 0 {
+        out.push(*triangle);
+        return;
+    }
+    // Todos fuera del mismo plano: descartar
+    if codes[0] & codes[1] & codes[2] != 0 {
+        return;
+    }
+
+    let mut polygon: Vec<ClipVertex> = triangle.to_vec();
+    let mut scratch: Vec<ClipVertex> = Vec::with_capacity(9);
+    let crossed = codes[0] | codes[1] | codes[2];
+
+    for plane in ClipPlane::ALL.iter().filter(|plane| crossed & plane.bit() != 0) {
+        clip_polygon_against(&polygon, *plane, &mut scratch);
+        std::mem::swap(&mut polygon, &mut scratch);
+        if polygon.len() < 3 {
+            return;
+        }
+    }
+
+    for i in 1..polygon.len() - 1 {
+        out.push([polygon[0], polygon[i], polygon[i + 1]]);
+    }
+}
+
+/// Recorta un segmento contra el frustum (Liang–Barsky)
+///
+/// Devuelve el tramo visible como parámetros `(t0, t1)` sobre `a -> b`
+/// (0 = `a`, 1 = `b`), o `None` si queda entero fuera. Al ser lineal en
+/// espacio de clip, los mismos parámetros sirven para interpolar en espacio mundo.
+pub fn clip_segment(a: &Vec4, b: &Vec4) -> Option<(f32, f32)> {
+    let (mut t0, mut t1) = (0.0f32, 1.0f32);
+    for plane in ClipPlane::ALL {
+        let d_a = plane.distance(a);
+        let d_b = plane.distance(b);
+        if d_a < 0.0 && d_b < 0.0 {
+            return None;
+        }
+        if d_a < 0.0 {
+            t0 = t0.max(d_a / (d_a - d_b));
+        } else if d_b < 0.0 {
+            t1 = t1.min(d_a / (d_a - d_b));
+        }
+    }
+    if t0 < t1 { Some((t0, t1)) } else { None }
+}
+
+/// Una pasada de Sutherland–Hodgman contra un plano
+fn clip_polygon_against(input: &[ClipVertex], plane: ClipPlane, output: &mut Vec<ClipVertex>) {
+    output.clear();
+    for (i, current) in input.iter().enumerate() {
+        let next = &input[(i + 1) % input.len()];
+        let d_current = plane.distance(&current.position);
+        let d_next = plane.distance(&next.position);
+
+        if d_current >= 0.0 {
+            output.push(*current);
+        }
+        // La arista cruza el plano: agregar la intersección
+        if (d_current >= 0.0) != (d_next >= 0.0) {
+            let t = d_current / (d_current - d_next);
+            output.push(ClipVertex::lerp(current, next, t));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vertex(x: f32, y: f32, z: f32, w: f32) -> ClipVertex {
+        ClipVertex {
+            position: Vec4::new(x, y, z, w),
+            normal: Vec3::zero(),
+            world: Vec3::zero(),
+            local: Vec3::zero(),
+        }
+    }
+
+    fn clip(triangle: [ClipVertex; 3]) -> Vec<[ClipVertex; 3]> {
+        let mut out = Vec::new();
+        clip_triangle(&triangle, &mut out);
+        out
+    }
+
+    /// Todos los vértices quedan dentro del frustum (con tolerancia de redondeo)
+    fn assert_inside(triangles: &[[ClipVertex; 3]]) {
+        for vertex in triangles.iter().flatten() {
+            for plane in ClipPlane::ALL {
+                assert!(plane.distance(&vertex.position) >= -1e-5, "{:?} fuera de {:?}", vertex.position, plane);
+            }
+        }
+    }
+
+    #[test]
+    fn triangle_inside_passes_unchanged() {
+        let triangle = [vertex(0.0, 0.0, 0.0, 1.0), vertex(0.5, 0.0, 0.0, 1.0), vertex(0.0, 0.5, 0.0, 1.0)];
+        let out = clip(triangle);
+        assert_eq!(out.len(), 1);
+        assert_eq!(out[0][1].position.x, 0.5);
+    }
+
+    #[test]
+    fn triangle_crossing_near_plane() {
+        // Un vértice delante del plano cercano: queda un triángulo más chico
+        let out = clip([vertex(0.0, 0.0, 0.0, 1.0), vertex(0.5, 0.0, -3.0, 1.0), vertex(0.0, 0.5, -3.0, 1.0)]);
+        assert_eq!(out.len(), 1);
+        assert_inside(&out);
+
+        // Dos vértices delante: el cuadrilátero se divide en dos triángulos
+        let out = clip([vertex(0.0, 0.0, 0.0, 1.0), vertex(0.5, 0.0, 0.0, 1.0), vertex(0.0, 0.5, -3.0, 1.0)]);
+        assert_eq!(out.len(), 2);
+        assert_inside(&out);
+
+        // Todos detrás: se descarta
+        let out = clip([vertex(0.0, 0.0, -3.0, 1.0), vertex(0.5, 0.0, -3.0, 1.0), vertex(0.0, 0.5, -3.0, 1.0)]);
+        assert!(out.is_empty());
+    }
+
+    #[test]
+    fn segment_behind_camera_is_cut() {
+        // `b` está detrás de la cámara (w < 0): el tramo visible termina antes
+        // de que w cambie de signo
+        let a = Vec4::new(0.0, 0.0, 0.0, 1.0);
+        let b = Vec4::new(0.0, 0.0, -3.0, -1.0);
+        let (t0, t1) = clip_segment(&a, &b).expect("a es visible");
+        assert_eq!(t0, 0.0);
+        assert!((t1 - 0.2).abs() < 1e-6, "t1 = {}", t1);
+        assert!(Vec4::lerp(&a, &b, t1).w > 0.0);
+
+        // Al revés da el mismo tramo desde el otro extremo
+        let (t0, t1) = clip_segment(&b, &a).expect("a es visible");
+        assert!((t0 - 0.8).abs() < 1e-6 && t1 == 1.0, "({}, {})", t0, t1);
+    }
+
+    #[test]
+    fn segment_outside_is_rejected() {
+        // Ambos extremos fuera del mismo plano
+        assert_eq!(clip_segment(&Vec4::new(2.0, 0.0, 0.0, 1.0), &Vec4::new(3.0, 0.5, 0.0, 1.0)), None);
+        // Extremos fuera de planos distintos, pasando junto a la esquina
+        assert_eq!(clip_segment(&Vec4::new(2.0, 0.0, 0.0, 1.0), &Vec4::new(0.0, 2.5, 0.0, 1.0)), None);
+        // Entero detrás de la cámara
+        assert_eq!(clip_segment(&Vec4::new(0.0, 0.0, -1.0, -1.0), &Vec4::new(0.0, 0.0, -2.0, -2.0)), None);
+    }
+}
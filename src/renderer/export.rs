@@ -0,0 +1,216 @@
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use super::framebuffer::Framebuffer;
+
+/// Formatos de imagen soportados para exportar frames
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImageFormat {
+    Ppm,
+    Png,
+}
+
+impl ImageFormat {
+    /// Extensión de archivo asociada al formato
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ImageFormat::Ppm => "ppm",
+            ImageFormat::Png => "png",
+        }
+    }
+
+    /// Deduce el formato a partir de un nombre ("png", "ppm")
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "ppm" => Some(ImageFormat::Ppm),
+            "png" => Some(ImageFormat::Png),
+            _ => None,
+        }
+    }
+}
+
+/// Guarda el framebuffer en el formato indicado, creando el directorio si no existe
+pub fn save_frame(fb: &Framebuffer, path: &Path, format: ImageFormat) -> io::Result<()> {
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)?;
+        }
+    }
+    match format {
+        ImageFormat::Ppm => save_ppm(fb, path),
+        ImageFormat::Png => save_png(fb, path),
+    }
+}
+
+/// Guarda el framebuffer como PPM binario (P6)
+pub fn save_ppm(fb: &Framebuffer, path: &Path) -> io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    write!(out, "P6\n{} {}\n255\n", fb.width, fb.height)?;
+    out.write_all(&rgb_bytes(fb))?;
+    out.flush()
+}
+
+/// Guarda el framebuffer como PNG RGB de 8 bits
+///
+/// Usa bloques deflate sin compresión para no depender de crates externos.
+pub fn save_png(fb: &Framebuffer, path: &Path) -> io::Result<()> {
+    let mut out = BufWriter::new(File::create(path)?);
+    out.write_all(&[0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'])?;
+
+    // IHDR: ancho, alto, 8 bits por canal, color RGB, sin entrelazado
+    let mut ihdr = Vec::with_capacity(13);
+    ihdr.extend_from_slice(&(fb.width as u32).to_be_bytes());
+    ihdr.extend_from_slice(&(fb.height as u32).to_be_bytes());
+    ihdr.extend_from_slice(&[8, 2, 0, 0, 0]);
+    write_png_chunk(&mut out, b"IHDR", &ihdr)?;
+
+    // Cada scanline va precedida por el tipo de filtro (0 = ninguno)
+    let rgb = rgb_bytes(fb);
+    let row_len = fb.width * 3;
+    let mut raw = Vec::with_capacity((row_len + 1) * fb.height);
+    for row in rgb.chunks(row_len.max(1)) {
+        raw.push(0);
+        raw.extend_from_slice(row);
+    }
+    write_png_chunk(&mut out, b"IDAT", &zlib_stored(&raw))?;
+    write_png_chunk(&mut out, b"IEND", &[])?;
+    out.flush()
+}
+
+/// Extrae los canales RGB del buffer (formato 0xAARRGGBB)
+fn rgb_bytes(fb: &Framebuffer) -> Vec<u8> {
+    let mut bytes = Vec::with_capacity(fb.buffer.len() * 3);
+    for pixel in &fb.buffer {
+        bytes.push(((pixel >> 16) & 0xFF) as u8);
+        bytes.push(((pixel >> 8) & 0xFF) as u8);
+        bytes.push((pixel & 0xFF) as u8);
+    }
+    bytes
+}
+
+fn write_png_chunk(out: &mut impl Write, kind: &[u8; 4], data: &[u8]) -> io::Result<()> {
+    out.write_all(&(data.len() as u32).to_be_bytes())?;
+    out.write_all(kind)?;
+    out.write_all(data)?;
+    let crc = crc32(kind.iter().chain(data.iter()).copied());
+    out.write_all(&crc.to_be_bytes())
+}
+
+/// Envuelve los datos en un stream zlib con bloques deflate "stored"
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    const MAX_BLOCK: usize = 65535;
+    let mut out = Vec::with_capacity(data.len() + data.len() / MAX_BLOCK * 5 + 16);
+    out.extend_from_slice(&[0x78, 0x01]);
+
+    let mut blocks = data.chunks(MAX_BLOCK).peekable();
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xFF, 0xFF]);
+    }
+    while let Some(block) = blocks.next() {
+        let is_last = blocks.peek().is_none();
+        let len = block.len() as u16;
+        out.push(is_last as u8);
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+fn crc32(bytes: impl Iterator<Item = u8>) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for byte in bytes {
+        crc ^= byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB8_8320 & mask);
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    const MOD: u32 = 65521;
+    let mut a = 1u32;
+    let mut b = 0u32;
+    for chunk in data.chunks(5552) {
+        for &byte in chunk {
+            a += byte as u32;
+            b += a;
+        }
+        a %= MOD;
+        b %= MOD;
+    }
+    (b << 16) | a
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use super::super::framebuffer::rgb_to_u32;
+
+    /// Framebuffer de 3x2 con un píxel de cada color
+    fn test_frame() -> Framebuffer {
+        let mut fb = Framebuffer::new(3, 2);
+        let colors = [(255, 0, 0), (0, 255, 0), (0, 0, 255), (10, 20, 30), (255, 255, 255), (0, 0, 0)];
+        for (pixel, (r, g, b)) in fb.buffer.iter_mut().zip(colors) {
+            *pixel = rgb_to_u32(r, g, b);
+        }
+        fb
+    }
+
+    fn save_and_read(format: ImageFormat) -> Vec<u8> {
+        let path = std::env::temp_dir()
+            .join(format!("solar2_export_{}", std::process::id()))
+            .join(format!("frame.{}", format.extension()));
+        save_frame(&test_frame(), &path, format).unwrap();
+        let bytes = fs::read(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        bytes
+    }
+
+    #[test]
+    fn ppm_has_header_and_pixels() {
+        let bytes = save_and_read(ImageFormat::Ppm);
+        let header = b"P6\n3 2\n255\n";
+        assert_eq!(&bytes[..header.len()], header);
+        assert_eq!(bytes.len(), header.len() + 3 * 2 * 3);
+        assert_eq!(&bytes[header.len()..header.len() + 6], &[255, 0, 0, 0, 255, 0]);
+    }
+
+    #[test]
+    fn png_chunks_are_valid() {
+        let bytes = save_and_read(ImageFormat::Png);
+        assert_eq!(&bytes[..8], &[0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n']);
+
+        // Recorre los chunks comprobando el CRC de cada uno
+        let mut kinds = Vec::new();
+        let mut pos = 8;
+        while pos < bytes.len() {
+            let len = u32::from_be_bytes(bytes[pos..pos + 4].try_into().unwrap()) as usize;
+            let body = &bytes[pos + 4..pos + 8 + len]; // Tipo + datos
+            let crc = u32::from_be_bytes(bytes[pos + 8 + len..pos + 12 + len].try_into().unwrap());
+            assert_eq!(crc, crc32(body.iter().copied()), "CRC de {:?}", &body[..4]);
+
+            let kind = &body[..4];
+            if kind == b"IHDR" {
+                assert_eq!(&body[4..12], &[0, 0, 0, 3, 0, 0, 0, 2]);
+                assert_eq!(&body[12..], &[8, 2, 0, 0, 0]);
+            }
+            kinds.push(String::from_utf8(kind.to_vec()).unwrap());
+            pos += 12 + len;
+        }
+        assert_eq!(pos, bytes.len());
+        assert_eq!(kinds, ["IHDR", "IDAT", "IEND"]);
+    }
+
+    #[test]
+    fn checksums_match_reference_values() {
+        // Valores conocidos de zlib y del estándar PNG
+        assert_eq!(crc32(b"IEND".iter().copied()), 0xAE42_6082);
+        assert_eq!(adler32(b"Wikipedia"), 0x11E6_0398);
+    }
+}
@@ -0,0 +1,220 @@
+use super::framebuffer::Framebuffer;
+
+/// Tamaño de cada glifo en píxeles (sin escalar)
+pub const GLYPH_WIDTH: usize = 8;
+pub const GLYPH_HEIGHT: usize = 8;
+
+/// Separación entre líneas en píxeles (sin escalar)
+pub const LINE_SPACING: usize = 2;
+
+/// Fuente bitmap 8x8 para ASCII 32-126 (font8x8, dominio público)
+///
+/// Cada byte es una fila de arriba hacia abajo; el bit menos significativo es
+/// el píxel de la izquierda.
+const ASCII: [[u8; 8]; 95] = [
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // ' '
+    [0x18, 0x3C, 0x3C, 0x18, 0x18, 0x00, 0x18, 0x00], // !
+    [0x36, 0x36, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // "
+    [0x36, 0x36, 0x7F, 0x36, 0x7F, 0x36, 0x36, 0x00], // #
+    [0x0C, 0x3E, 0x03, 0x1E, 0x30, 0x1F, 0x0C, 0x00], // $
+    [0x00, 0x63, 0x33, 0x18, 0x0C, 0x66, 0x63, 0x00], // %
+    [0x1C, 0x36, 0x1C, 0x6E, 0x3B, 0x33, 0x6E, 0x00], // &
+    [0x06, 0x06, 0x03, 0x00, 0x00, 0x00, 0x00, 0x00], // '
+    [0x18, 0x0C, 0x06, 0x06, 0x06, 0x0C, 0x18, 0x00], // (
+    [0x06, 0x0C, 0x18, 0x18, 0x18, 0x0C, 0x06, 0x00], // )
+    [0x00, 0x66, 0x3C, 0xFF, 0x3C, 0x66, 0x00, 0x00], // *
+    [0x00, 0x0C, 0x0C, 0x3F, 0x0C, 0x0C, 0x00, 0x00], // +
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C, 0x06], // ,
+    [0x00, 0x00, 0x00, 0x3F, 0x00, 0x00, 0x00, 0x00], // -
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C, 0x00], // .
+    [0x60, 0x30, 0x18, 0x0C, 0x06, 0x03, 0x01, 0x00], // /
+    [0x3E, 0x63, 0x73, 0x7B, 0x6F, 0x67, 0x3E, 0x00], // 0
+    [0x0C, 0x0E, 0x0C, 0x0C, 0x0C, 0x0C, 0x3F, 0x00], // 1
+    [0x1E, 0x33, 0x30, 0x1C, 0x06, 0x33, 0x3F, 0x00], // 2
+    [0x1E, 0x33, 0x30, 0x1C, 0x30, 0x33, 0x1E, 0x00], // 3
+    [0x38, 0x3C, 0x36, 0x33, 0x7F, 0x30, 0x78, 0x00], // 4
+    [0x3F, 0x03, 0x1F, 0x30, 0x30, 0x33, 0x1E, 0x00], // 5
+    [0x1C, 0x06, 0x03, 0x1F, 0x33, 0x33, 0x1E, 0x00], // 6
+    [0x3F, 0x33, 0x30, 0x18, 0x0C, 0x0C, 0x0C, 0x00], // 7
+    [0x1E, 0x33, 0x33, 0x1E, 0x33, 0x33, 0x1E, 0x00], // 8
+    [0x1E, 0x33, 0x33, 0x3E, 0x30, 0x18, 0x0E, 0x00], // 9
+    [0x00, 0x0C, 0x0C, 0x00, 0x00, 0x0C, 0x0C, 0x00], // :
+    [0x00, 0x0C, 0x0C, 0x00, 0x00, 0x0C, 0x0C, 0x06], // ;
+    [0x18, 0x0C, 0x06, 0x03, 0x06, 0x0C, 0x18, 0x00], // <
+    [0x00, 0x00, 0x3F, 0x00, 0x00, 0x3F, 0x00, 0x00], // =
+    [0x06, 0x0C, 0x18, 0x30, 0x18, 0x0C, 0x06, 0x00], // >
+    [0x1E, 0x33, 0x30, 0x18, 0x0C, 0x00, 0x0C, 0x00], // ?
+    [0x3E, 0x63, 0x7B, 0x7B, 0x7B, 0x03, 0x1E, 0x00], // @
+    [0x0C, 0x1E, 0x33, 0x33, 0x3F, 0x33, 0x33, 0x00], // A
+    [0x3F, 0x66, 0x66, 0x3E, 0x66, 0x66, 0x3F, 0x00], // B
+    [0x3C, 0x66, 0x03, 0x03, 0x03, 0x66, 0x3C, 0x00], // C
+    [0x1F, 0x36, 0x66, 0x66, 0x66, 0x36, 0x1F, 0x00], // D
+    [0x7F, 0x46, 0x16, 0x1E, 0x16, 0x46, 0x7F, 0x00], // E
+    [0x7F, 0x46, 0x16, 0x1E, 0x16, 0x06, 0x0F, 0x00], // F
+    [0x3C, 0x66, 0x03, 0x03, 0x73, 0x66, 0x7C, 0x00], // G
+    [0x33, 0x33, 0x33, 0x3F, 0x33, 0x33, 0x33, 0x00], // H
+    [0x1E, 0x0C, 0x0C, 0x0C, 0x0C, 0x0C, 0x1E, 0x00], // I
+    [0x78, 0x30, 0x30, 0x30, 0x33, 0x33, 0x1E, 0x00], // J
+    [0x67, 0x66, 0x36, 0x1E, 0x36, 0x66, 0x67, 0x00], // K
+    [0x0F, 0x06, 0x06, 0x06, 0x46, 0x66, 0x7F, 0x00], // L
+    [0x63, 0x77, 0x7F, 0x7F, 0x6B, 0x63, 0x63, 0x00], // M
+    [0x63, 0x67, 0x6F, 0x7B, 0x73, 0x63, 0x63, 0x00], // N
+    [0x1C, 0x36, 0x63, 0x63, 0x63, 0x36, 0x1C, 0x00], // O
+    [0x3F, 0x66, 0x66, 0x3E, 0x06, 0x06, 0x0F, 0x00], // P
+    [0x1E, 0x33, 0x33, 0x33, 0x3B, 0x1E, 0x38, 0x00], // Q
+    [0x3F, 0x66, 0x66, 0x3E, 0x36, 0x66, 0x67, 0x00], // R
+    [0x1E, 0x33, 0x07, 0x0E, 0x38, 0x33, 0x1E, 0x00], // S
+    [0x3F, 0x2D, 0x0C, 0x0C, 0x0C, 0x0C, 0x1E, 0x00], // T
+    [0x33, 0x33, 0x33, 0x33, 0x33, 0x33, 0x3F, 0x00], // U
+    [0x33, 0x33, 0x33, 0x33, 0x33, 0x1E, 0x0C, 0x00], // V
+    [0x63, 0x63, 0x63, 0x6B, 0x7F, 0x77, 0x63, 0x00], // W
+    [0x63, 0x63, 0x36, 0x1C, 0x1C, 0x36, 0x63, 0x00], // X
+    [0x33, 0x33, 0x33, 0x1E, 0x0C, 0x0C, 0x1E, 0x00], // Y
+    [0x7F, 0x63, 0x31, 0x18, 0x4C, 0x66, 0x7F, 0x00], // Z
+    [0x1E, 0x06, 0x06, 0x06, 0x06, 0x06, 0x1E, 0x00], // [
+    [0x03, 0x06, 0x0C, 0x18, 0x30, 0x60, 0x40, 0x00], // \
+    [0x1E, 0x18, 0x18, 0x18, 0x18, 0x18, 0x1E, 0x00], // ]
+    [0x08, 0x1C, 0x36, 0x63, 0x00, 0x00, 0x00, 0x00], // ^
+    [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0xFF], // _
+    [0x0C, 0x0C, 0x18, 0x00, 0x00, 0x00, 0x00, 0x00], // `
+    [0x00, 0x00, 0x1E, 0x30, 0x3E, 0x33, 0x6E, 0x00], // a
+    [0x07, 0x06, 0x06, 0x3E, 0x66, 0x66, 0x3B, 0x00], // b
+    [0x00, 0x00, 0x1E, 0x33, 0x03, 0x33, 0x1E, 0x00], // c
+    [0x38, 0x30, 0x30, 0x3E, 0x33, 0x33, 0x6E, 0x00], // d
+    [0x00, 0x00, 0x1E, 0x33, 0x3F, 0x03, 0x1E, 0x00], // e
+    [0x1C, 0x36, 0x06, 0x0F, 0x06, 0x06, 0x0F, 0x00], // f
+    [0x00, 0x00, 0x6E, 0x33, 0x33, 0x3E, 0x30, 0x1F], // g
+    [0x07, 0x06, 0x36, 0x6E, 0x66, 0x66, 0x67, 0x00], // h
+    [0x0C, 0x00, 0x0E, 0x0C, 0x0C, 0x0C, 0x1E, 0x00], // i
+    [0x30, 0x00, 0x30, 0x30, 0x30, 0x33, 0x33, 0x1E], // j
+    [0x07, 0x06, 0x66, 0x36, 0x1E, 0x36, 0x67, 0x00], // k
+    [0x0E, 0x0C, 0x0C, 0x0C, 0x0C, 0x0C, 0x1E, 0x00], // l
+    [0x00, 0x00, 0x33, 0x7F, 0x7F, 0x6B, 0x63, 0x00], // m
+    [0x00, 0x00, 0x1F, 0x33, 0x33, 0x33, 0x33, 0x00], // n
+    [0x00, 0x00, 0x1E, 0x33, 0x33, 0x33, 0x1E, 0x00], // o
+    [0x00, 0x00, 0x3B, 0x66, 0x66, 0x3E, 0x06, 0x0F], // p
+    [0x00, 0x00, 0x6E, 0x33, 0x33, 0x3E, 0x30, 0x78], // q
+    [0x00, 0x00, 0x3B, 0x6E, 0x66, 0x06, 0x0F, 0x00], // r
+    [0x00, 0x00, 0x3E, 0x03, 0x1E, 0x30, 0x1F, 0x00], // s
+    [0x08, 0x0C, 0x3E, 0x0C, 0x0C, 0x2C, 0x18, 0x00], // t
+    [0x00, 0x00, 0x33, 0x33, 0x33, 0x33, 0x6E, 0x00], // u
+    [0x00, 0x00, 0x33, 0x33, 0x33, 0x1E, 0x0C, 0x00], // v
+    [0x00, 0x00, 0x63, 0x6B, 0x7F, 0x7F, 0x36, 0x00], // w
+    [0x00, 0x00, 0x63, 0x36, 0x1C, 0x36, 0x63, 0x00], // x
+    [0x00, 0x00, 0x33, 0x33, 0x33, 0x3E, 0x30, 0x1F], // y
+    [0x00, 0x00, 0x3F, 0x19, 0x0C, 0x26, 0x3F, 0x00], // z
+    [0x38, 0x0C, 0x0C, 0x07, 0x0C, 0x0C, 0x38, 0x00], // {
+    [0x18, 0x18, 0x18, 0x00, 0x18, 0x18, 0x18, 0x00], // |
+    [0x07, 0x0C, 0x0C, 0x38, 0x0C, 0x0C, 0x07, 0x00], // }
+    [0x6E, 0x3B, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00], // ~
+];
+
+/// Acentos para las minúsculas del español (dos filas sobre la letra)
+const ACUTE: [u8; 2] = [0x30, 0x18];
+const TILDE: [u8; 2] = [0x6E, 0x3B];
+const DIAERESIS: [u8; 2] = [0x33, 0x00];
+
+/// Glifo de un carácter; los que no están en la fuente se dibujan como '?'
+pub fn glyph(c: char) -> [u8; 8] {
+    match c {
+        ' '..='~' => ASCII[c as usize - ' ' as usize],
+        'á' => accented('a', ACUTE),
+        'é' => accented('e', ACUTE),
+        'í' => accented('i', ACUTE),
+        'ó' => accented('o', ACUTE),
+        'ú' => accented('u', ACUTE),
+        'ñ' => accented('n', TILDE),
+        'ü' => accented('u', DIAERESIS),
+        // Las mayúsculas no tienen lugar para el acento
+        'Á' => glyph('A'),
+        'É' => glyph('E'),
+        'Í' => glyph('I'),
+        'Ó' => glyph('O'),
+        'Ú' | 'Ü' => glyph('U'),
+        'Ñ' => glyph('N'),
+        '¿' => [0x0C, 0x00, 0x0C, 0x06, 0x03, 0x33, 0x1E, 0x00],
+        '¡' => [0x18, 0x00, 0x18, 0x18, 0x3C, 0x3C, 0x18, 0x00],
+        '°' => [0x1C, 0x36, 0x1C, 0x00, 0x00, 0x00, 0x00, 0x00],
+        '·' => [0x00, 0x00, 0x00, 0x0C, 0x0C, 0x00, 0x00, 0x00],
+        _ => glyph('?'),
+    }
+}
+
+/// Minúscula con un acento en las dos filas libres de arriba
+fn accented(base: char, accent: [u8; 2]) -> [u8; 8] {
+    let mut rows = glyph(base);
+    rows[0] = accent[0];
+    rows[1] = accent[1];
+    rows
+}
+
+/// Ancho en píxeles de la línea más larga de `text`
+pub fn text_width(text: &str, scale: usize) -> usize {
+    text.lines().map(|line| line.chars().count()).max().unwrap_or(0) * GLYPH_WIDTH * scale
+}
+
+/// Alto en píxeles de `text` (con separación entre líneas)
+pub fn text_height(text: &str, scale: usize) -> usize {
+    let lines = text.lines().count().max(1);
+    (lines * (GLYPH_HEIGHT + LINE_SPACING) - LINE_SPACING) * scale
+}
+
+/// Dibuja `text` con la esquina superior izquierda en (x, y), sin z-test
+///
+/// `scale` agranda cada píxel del glifo; los saltos de línea bajan una línea.
+/// Lo que cae fuera del framebuffer se recorta.
+pub fn draw_text(fb: &mut Framebuffer, x: i32, y: i32, text: &str, color: u32, scale: usize) {
+    layout(fb, x, y, text, scale, |fb, px, py| fb.set_pixel_no_depth(px, py, color));
+}
+
+/// Como `draw_text`, pero mezclando el texto con el fondo (`alpha` de 0 a 1)
+pub fn draw_text_alpha(fb: &mut Framebuffer, x: i32, y: i32, text: &str, color: u32, scale: usize, alpha: f32) {
+    layout(fb, x, y, text, scale, |fb, px, py| fb.blend_pixel(px, py, color, alpha));
+}
+
+/// Dibuja `text` con una sombra de un píxel para que se lea sobre cualquier fondo
+pub fn draw_text_shadowed(fb: &mut Framebuffer, x: i32, y: i32, text: &str, color: u32, scale: usize) {
+    let offset = scale.max(1) as i32;
+    draw_text(fb, x + offset, y + offset, text, 0xFF000000, scale);
+    draw_text(fb, x, y, text, color, scale);
+}
+
+/// Ubica cada glifo de `text` en líneas y columnas y pinta sus píxeles con `plot`
+fn layout(fb: &mut Framebuffer, x: i32, y: i32, text: &str, scale: usize, plot: impl Fn(&mut Framebuffer, usize, usize)) {
+    let scale = scale.max(1);
+    let line_height = ((GLYPH_HEIGHT + LINE_SPACING) * scale) as i32;
+    for (row, line) in text.lines().enumerate() {
+        let line_y = y + row as i32 * line_height;
+        for (column, c) in line.chars().enumerate() {
+            let glyph_x = x + (column * GLYPH_WIDTH * scale) as i32;
+            draw_glyph(fb, glyph_x, line_y, glyph(c), scale, &plot);
+        }
+    }
+}
+
+/// Recorre los píxeles encendidos de un glifo y los pinta con `plot`
+fn draw_glyph(
+    fb: &mut Framebuffer,
+    x: i32,
+    y: i32,
+    rows: [u8; 8],
+    scale: usize,
+    plot: &impl Fn(&mut Framebuffer, usize, usize),
+) {
+    for (gy, bits) in rows.iter().enumerate() {
+        for gx in 0..GLYPH_WIDTH {
+            if bits & (1 << gx) == 0 {
+                continue;
+            }
+            for sy in 0..scale {
+                for sx in 0..scale {
+                    let px = x + (gx * scale + sx) as i32;
+                    let py = y + (gy * scale + sy) as i32;
+                    if px >= 0 && py >= 0 {
+                        plot(fb, px as usize, py as usize);
+                    }
+                }
+            }
+        }
+    }
+}
@@ -1,3 +1,5 @@
+use rayon::prelude::*;
+
 /// Framebuffer con soporte para z-buffer
 pub struct Framebuffer {
     pub width: usize,
@@ -5,6 +7,9 @@ pub struct Framebuffer {
     pub buffer: Vec<u32>,      // Color buffer (ARGB)
     pub zbuffer: Vec<f32>,     // Depth buffer
     pub background_color: u32,
+    pub samples: usize,        // Muestras por píxel de los cuerpos (1 = sin MSAA)
+    pub sample_color: Vec<u32>, // Color por muestra (vacío sin MSAA)
+    pub sample_depth: Vec<f32>, // Profundidad por muestra (vacío sin MSAA)
 }
 
 impl Framebuffer {
@@ -16,7 +21,63 @@ impl Framebuffer {
             buffer: vec![0x000000; size],
             zbuffer: vec![f32::INFINITY; size],
             background_color: 0x000510, // Azul muy oscuro para el espacio
+            samples: 1,
+            sample_color: Vec::new(),
+            sample_depth: Vec::new(),
+        }
+    }
+
+    /// Cambia las muestras por píxel (1 = sin MSAA) y reserva sus buffers
+    pub fn set_samples(&mut self, samples: usize) {
+        let samples = samples.max(1);
+        if samples == self.samples {
+            return;
+        }
+        self.samples = samples;
+        let size = if samples > 1 { self.width * self.height * samples } else { 0 };
+        self.sample_color = vec![0; size];
+        self.sample_depth = vec![f32::INFINITY; size];
+    }
+
+    /// Vacía la profundidad de las muestras antes de rasterizar
+    pub fn clear_samples(&mut self) {
+        for depth in self.sample_depth.iter_mut() {
+            *depth = f32::INFINITY;
+        }
+    }
+
+    /// Combina las muestras en el color y la profundidad de cada píxel
+    ///
+    /// Las muestras que ningún triángulo cubrió toman el color que ya tenía el
+    /// píxel (el skybox), así los bordes se mezclan con el fondo. El
+    /// z-buffer queda con la muestra más cercana.
+    pub fn resolve_samples(&mut self) {
+        let samples = self.samples;
+        if samples <= 1 {
+            return;
         }
+        let colors = self.sample_color.par_chunks(samples);
+        let depths = self.sample_depth.par_chunks(samples);
+        self.buffer
+            .par_iter_mut()
+            .zip(self.zbuffer.par_iter_mut())
+            .zip(colors.zip(depths))
+            .for_each(|((pixel, depth), (colors, depths))| {
+                let covered = depths.iter().filter(|d| d.is_finite()).count();
+                if covered == 0 {
+                    return;
+                }
+                let (mut r, mut g, mut b) = (0u32, 0u32, 0u32);
+                for (&color, &sample_depth) in colors.iter().zip(depths) {
+                    let color = if sample_depth.is_finite() { color } else { *pixel };
+                    r += (color >> 16) & 0xFF;
+                    g += (color >> 8) & 0xFF;
+                    b += color & 0xFF;
+                }
+                let n = samples as u32;
+                *pixel = rgb_to_u32((r / n) as u8, (g / n) as u8, (b / n) as u8);
+                *depth = depths.iter().copied().fold(*depth, f32::min);
+            });
     }
 
     /// Limpia el buffer con el color de fondo
@@ -29,6 +90,13 @@ impl Framebuffer {
         }
     }
 
+    /// Limpia solo el depth buffer (mantiene los colores, p. ej. el skybox)
+    pub fn clear_depth(&mut self) {
+        for depth in self.zbuffer.iter_mut() {
+            *depth = f32::INFINITY;
+        }
+    }
+
     /// Establece un píxel con z-test
     pub fn set_pixel(&mut self, x: usize, y: usize, z: f32, color: u32) {
         if x < self.width && y < self.height {
@@ -40,7 +108,7 @@ impl Framebuffer {
         }
     }
 
-    /// Establece un píxel sin z-test (para elementos 2D como órbitas)
+    /// Establece un píxel sin z-test (para elementos 2D como el texto)
     pub fn set_pixel_no_depth(&mut self, x: usize, y: usize, color: u32) {
         if x < self.width && y < self.height {
             let index = y * self.width + x;
@@ -48,10 +116,118 @@ impl Framebuffer {
         }
     }
 
+    /// Mezcla `color` sobre el píxel con opacidad `alpha` (0 a 1), sin z-test
+    pub fn blend_pixel(&mut self, x: usize, y: usize, color: u32, alpha: f32) {
+        if x < self.width && y < self.height {
+            let index = y * self.width + x;
+            let alpha = alpha.clamp(0.0, 1.0);
+            let mix = |shift: u32| {
+                let under = ((self.buffer[index] >> shift) & 0xFF) as f32;
+                let over = ((color >> shift) & 0xFF) as f32;
+                (under + (over - under) * alpha).round() as u8
+            };
+            self.buffer[index] = rgb_to_u32(mix(16), mix(8), mix(0));
+        }
+    }
+
+    /// Mezcla `color` sobre el píxel si `z` pasa el z-test, sin escribir la
+    /// profundidad (líneas que no deben tapar a nada)
+    pub fn blend_pixel_tested(&mut self, x: usize, y: usize, z: f32, color: u32, alpha: f32) {
+        if x < self.width && y < self.height && z < self.zbuffer[y * self.width + x] {
+            self.blend_pixel(x, y, color, alpha);
+        }
+    }
+
     /// Obtiene el índice en el buffer
     pub fn get_index(&self, x: usize, y: usize) -> usize {
         y * self.width + x
     }
+
+    /// Divide el framebuffer en bandas horizontales de `rows` filas
+    ///
+    /// Cada banda es una vista mutable independiente, por lo que se pueden
+    /// rasterizar en paralelo.
+    pub fn bands_mut(&mut self, rows: usize) -> Vec<FramebufferBand<'_>> {
+        let rows = rows.max(1);
+        let width = self.width;
+        let height = self.height;
+        let chunk = (width * rows).max(1);
+        let samples = self.samples;
+
+        // Sin MSAA cada banda recibe buffers de muestras vacíos
+        let band_count = height.div_ceil(rows);
+        let (sample_colors, sample_depths): (Vec<&mut [u32]>, Vec<&mut [f32]>) = if samples > 1 {
+            (
+                self.sample_color.chunks_mut(chunk * samples).collect(),
+                self.sample_depth.chunks_mut(chunk * samples).collect(),
+            )
+        } else {
+            (
+                (0..band_count).map(|_| Default::default()).collect(),
+                (0..band_count).map(|_| Default::default()).collect(),
+            )
+        };
+
+        self.buffer
+            .chunks_mut(chunk)
+            .zip(self.zbuffer.chunks_mut(chunk))
+            .zip(sample_colors.into_iter().zip(sample_depths))
+            .enumerate()
+            .map(|(i, ((buffer, zbuffer), (sample_color, sample_depth)))| FramebufferBand {
+                width,
+                height,
+                y_start: i * rows,
+                y_end: (i * rows + rows).min(height),
+                buffer,
+                zbuffer,
+                samples,
+                sample_color,
+                sample_depth,
+            })
+            .collect()
+    }
+}
+
+/// Vista mutable de un rango de filas [y_start, y_end) del framebuffer
+pub struct FramebufferBand<'a> {
+    pub width: usize,
+    pub height: usize, // Alto total del framebuffer
+    pub y_start: usize,
+    pub y_end: usize,
+    pub buffer: &'a mut [u32],
+    pub zbuffer: &'a mut [f32],
+    pub samples: usize,
+    pub sample_color: &'a mut [u32],
+    pub sample_depth: &'a mut [f32],
+}
+
+impl FramebufferBand<'_> {
+    /// Establece un píxel con z-test (y en coordenadas del framebuffer completo)
+    pub fn set_pixel(&mut self, x: usize, y: usize, z: f32, color: u32) {
+        if x < self.width && y >= self.y_start && y < self.y_end {
+            let index = (y - self.y_start) * self.width + x;
+            if z < self.zbuffer[index] {
+                self.zbuffer[index] = z;
+                self.buffer[index] = color;
+            }
+        }
+    }
+
+    /// Escribe una muestra de MSAA con z-test
+    pub fn set_sample(&mut self, x: usize, y: usize, sample: usize, z: f32, color: u32) {
+        if x < self.width && y >= self.y_start && y < self.y_end && sample < self.samples {
+            let index = ((y - self.y_start) * self.width + x) * self.samples + sample;
+            if z < self.sample_depth[index] {
+                self.sample_depth[index] = z;
+                self.sample_color[index] = color;
+            }
+        }
+    }
+
+    /// Profundidad guardada en una muestra
+    pub fn sample_depth(&self, x: usize, y: usize, sample: usize) -> f32 {
+        self.sample_depth[((y - self.y_start) * self.width + x) * self.samples + sample]
+    }
 }
 
 /// Convierte RGB a u32 (formato 0xAARRGGBB)
@@ -0,0 +1,175 @@
+use super::font::{self, GLYPH_HEIGHT, GLYPH_WIDTH, LINE_SPACING};
+use super::framebuffer::{Framebuffer, rgb_to_u32};
+use super::pipeline::Antialiasing;
+use crate::camera::{Camera, CameraMode};
+use crate::scene::{SimulationClock, SolarSystem};
+
+/// Márgenes del HUD en píxeles
+const MARGIN: i32 = 10;
+const PADDING: i32 = 6;
+
+/// Altura de una línea de texto a escala 1
+const LINE_HEIGHT: i32 = (GLYPH_HEIGHT + LINE_SPACING) as i32;
+
+/// Segundos que se muestra un aviso
+const NOTICE_SECONDS: f32 = 3.0;
+
+/// Colores del HUD
+const TEXT_COLOR: u32 = 0xFFE6ECFF;
+const TITLE_COLOR: u32 = 0xFFFFD27A;
+const DIM_COLOR: u32 = 0xFF8C96B4;
+
+/// Información en pantalla: objetivo, distancia, rendimiento, reloj y ayuda
+///
+/// Se dibuja sobre el framebuffer ya renderizado, sin z-test.
+#[derive(Debug, Clone)]
+pub struct Hud {
+    pub visible: bool,
+    pub show_help: bool,
+    pub help: Vec<(String, String)>, // Filas (teclas, descripción) de la ayuda
+    pub hint: String,                 // Línea al pie, p. ej. "F1 ayuda"
+    pub render_scale: f32,            // Escala de render interna, para mostrarla
+    pub antialiasing: Antialiasing,   // Modo de anti-aliasing, para mostrarlo
+    frame_time: f32,                  // Duración promedio del frame (segundos)
+    notice: Option<(String, f32)>,    // Aviso y segundos restantes
+}
+
+impl Hud {
+    pub fn new(help: Vec<(String, String)>, hint: String) -> Self {
+        Self {
+            visible: true,
+            show_help: false,
+            help,
+            hint,
+            render_scale: 1.0,
+            antialiasing: Antialiasing::None,
+            frame_time: 0.0,
+            notice: None,
+        }
+    }
+
+    /// Registra la duración real de un frame (promedio exponencial)
+    pub fn record_frame(&mut self, delta_time: f32) {
+        if self.frame_time <= 0.0 {
+            self.frame_time = delta_time;
+        } else {
+            self.frame_time += (delta_time - self.frame_time) * 0.1;
+        }
+        if let Some((_, remaining)) = &mut self.notice {
+            *remaining -= delta_time;
+            if *remaining <= 0.0 {
+                self.notice = None;
+            }
+        }
+    }
+
+    pub fn fps(&self) -> f32 {
+        if self.frame_time > 0.0 { 1.0 / self.frame_time } else { 0.0 }
+    }
+
+    /// Duración promedio del frame en milisegundos
+    pub fn frame_time_ms(&self) -> f32 {
+        self.frame_time * 1000.0
+    }
+
+    /// Muestra un aviso temporal (p. ej. "Snapshot guardado")
+    pub fn notify(&mut self, message: impl Into<String>) {
+        self.notice = Some((message.into(), NOTICE_SECONDS));
+    }
+
+    /// Dibuja el HUD
+    pub fn draw(
+        &self,
+        fb: &mut Framebuffer,
+        solar_system: &SolarSystem,
+        camera: &Camera,
+        target: usize,
+        clock: &SimulationClock,
+    ) {
+        if !self.visible {
+            return;
+        }
+
+        // Objetivo y estado de la cámara (arriba a la izquierda)
+        let body = &solar_system.bodies[target];
+        let distance = (camera.position - solar_system.get_body_position(target)).length();
+        let mut lines = vec![
+            format!("Distancia: {:.1} (altitud {:.1})", distance, distance - body.radius),
+            format!("Tiempo: {}", clock.status()),
+        ];
+        if solar_system.physics_enabled() {
+            lines.push("Gravedad N-cuerpos".to_string());
+        }
+        lines.push(match (&camera.warp, camera.mode) {
+            (Some(warp), _) => format!("Viajando... {:.0}%", warp.progress() * 100.0),
+            (None, CameraMode::FreeFlight) => format!("Vuelo libre: {:.1} u/s", camera.speed()),
+            (None, CameraMode::Orbit) => "Órbita".to_string(),
+        });
+
+        let text = lines.join("\n");
+        let width = font::text_width(&body.name, 2).max(font::text_width(&text, 1)) as i32;
+        let title_height = 2 * LINE_HEIGHT;
+        let height = title_height + font::text_height(&text, 1) as i32;
+        shade_rect(fb, MARGIN, MARGIN, width + 2 * PADDING, height + 2 * PADDING);
+        let (x, y) = (MARGIN + PADDING, MARGIN + PADDING);
+        font::draw_text_shadowed(fb, x, y, &body.name, TITLE_COLOR, 2);
+        font::draw_text_shadowed(fb, x, y + title_height, &text, TEXT_COLOR, 1);
+        let mut bottom = MARGIN + height + 2 * PADDING;
+
+        // Rendimiento (arriba a la derecha)
+        let stats = format!(
+            "{:.0} FPS\n{:.1} ms\n{}x{} @ {:.0}%\n{}",
+            self.fps(),
+            self.frame_time_ms(),
+            fb.width,
+            fb.height,
+            self.render_scale * 100.0,
+            self.antialiasing.label()
+        );
+        let stats_x = fb.width as i32 - MARGIN - PADDING - font::text_width(&stats, 1) as i32;
+        font::draw_text_shadowed(fb, stats_x, MARGIN + PADDING, &stats, TEXT_COLOR, 1);
+
+        // Ayuda de controles debajo del panel del objetivo
+        if self.show_help {
+            bottom += MARGIN;
+            self.draw_help(fb, MARGIN, bottom);
+        }
+
+        // Aviso y pie (abajo)
+        let footer_y = fb.height as i32 - MARGIN - LINE_HEIGHT;
+        font::draw_text_shadowed(fb, MARGIN, footer_y, &self.hint, DIM_COLOR, 1);
+        if let Some((message, _)) = &self.notice {
+            let x = (fb.width as i32 - font::text_width(message, 1) as i32) / 2;
+            font::draw_text_shadowed(fb, x, footer_y - 2 * LINE_HEIGHT, message, TITLE_COLOR, 1);
+        }
+    }
+
+    /// Tabla de controles en dos columnas
+    fn draw_help(&self, fb: &mut Framebuffer, x: i32, y: i32) {
+        let keys_width = self.help.iter().map(|(keys, _)| font::text_width(keys, 1)).max().unwrap_or(0) as i32;
+        let descriptions_width = self.help.iter().map(|(_, text)| font::text_width(text, 1)).max().unwrap_or(0) as i32;
+        let gap = 2 * GLYPH_WIDTH as i32;
+        let height = self.help.len() as i32 * LINE_HEIGHT - LINE_SPACING as i32;
+        shade_rect(fb, x, y, keys_width + gap + descriptions_width + 2 * PADDING, height + 2 * PADDING);
+
+        for (i, (keys, description)) in self.help.iter().enumerate() {
+            let row_y = y + PADDING + i as i32 * LINE_HEIGHT;
+            font::draw_text_shadowed(fb, x + PADDING, row_y, keys, TITLE_COLOR, 1);
+            font::draw_text_shadowed(fb, x + PADDING + keys_width + gap, row_y, description, TEXT_COLOR, 1);
+        }
+    }
+}
+
+/// Oscurece un rectángulo para que el texto se lea sobre planetas brillantes
+fn shade_rect(fb: &mut Framebuffer, x: i32, y: i32, width: i32, height: i32) {
+    let x0 = x.clamp(0, fb.width as i32) as usize;
+    let y0 = y.clamp(0, fb.height as i32) as usize;
+    let x1 = (x + width).clamp(0, fb.width as i32) as usize;
+    let y1 = (y + height).clamp(0, fb.height as i32) as usize;
+    for py in y0..y1 {
+        for pixel in &mut fb.buffer[py * fb.width + x0..py * fb.width + x1] {
+            let (r, g, b) = ((*pixel >> 16) & 0xFF, (*pixel >> 8) & 0xFF, *pixel & 0xFF);
+            *pixel = rgb_to_u32((r * 2 / 5) as u8, (g * 2 / 5) as u8, (b * 2 / 5) as u8);
+        }
+    }
+}
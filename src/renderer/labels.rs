@@ -0,0 +1,196 @@
+use crate::camera::Camera;
+use crate::math::{Mat4, Vec3, project_to_screen};
+use crate::scene::SolarSystem;
+use crate::shaders::smoothstep;
+use super::font::{self, GLYPH_HEIGHT};
+use super::framebuffer::{Framebuffer, rgb_f32_to_u32};
+use super::triangle::walk_line;
+
+/// Parámetros de las etiquetas con el nombre de los cuerpos
+#[derive(Debug, Clone, Copy)]
+pub struct LabelSettings {
+    pub enabled: bool,
+    pub fade_start: f32,    // Distancia a la cámara donde empieza a desvanecerse
+    pub fade_end: f32,      // Distancia a la que ya no se dibuja
+    pub leader_length: f32, // Largo de la línea guía en píxeles (por eje)
+}
+
+impl Default for LabelSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            fade_start: 60.0,
+            fade_end: 160.0,
+            leader_length: 12.0,
+        }
+    }
+}
+
+/// Candidata a etiqueta antes de ubicarla
+struct Candidate {
+    body: usize,
+    center: (f32, f32), // Centro proyectado del cuerpo
+    radius: f32,        // Radio aparente en píxeles
+    distance: f32,      // Distancia a la cámara
+    alpha: f32,
+    color: u32,         // Color del cuerpo aclarado
+}
+
+/// Rectángulo ocupado en pantalla (x0, y0, x1, y1)
+type Rect = (i32, i32, i32, i32);
+
+/// Dibuja el nombre de cada cuerpo con una línea guía hasta él
+///
+/// `depth` es el framebuffer donde ya se rasterizaron los cuerpos (puede tener
+/// otra resolución que `fb`): una etiqueta se oculta si su z-buffer tiene algo
+/// delante de su cuerpo. Los cuerpos de mayor tamaño
+/// aparente eligen lugar primero; si una etiqueta no entra sin solaparse con
+/// otra en ninguna de las cuatro esquinas, se omite.
+pub fn render_labels(
+    fb: &mut Framebuffer,
+    depth: &Framebuffer,
+    solar_system: &SolarSystem,
+    camera: &Camera,
+    settings: &LabelSettings,
+) {
+    if !settings.enabled {
+        return;
+    }
+    let vp_matrix = camera.view_projection_matrix();
+    let pixels_per_unit = fb.height as f32 * 0.5 / (camera.fov * 0.5).tan();
+
+    let mut candidates = Vec::new();
+    for (i, body) in solar_system.bodies.iter().enumerate() {
+        if !body.show_label {
+            continue;
+        }
+        let position = solar_system.get_body_position(i);
+        let to_camera = camera.position - position;
+        let distance = to_camera.length();
+        if distance <= body.radius {
+            continue;
+        }
+
+        let alpha = 1.0 - smoothstep(settings.fade_start, settings.fade_end, distance);
+        if alpha < 0.02 {
+            continue;
+        }
+
+        let (x, y, _) = match project_to_screen(position, &vp_matrix, fb.width, fb.height) {
+            Some(p) => p,
+            None => continue,
+        };
+        if x < 0 || y < 0 || x >= fb.width as i32 || y >= fb.height as i32 {
+            continue;
+        }
+
+        // Punto del cuerpo más cercano a la cámara, para compararlo con el z-buffer
+        let front = position + to_camera * (body.radius / distance);
+        let pixel = (x as usize * depth.width / fb.width, y as usize * depth.height / fb.height);
+        if is_occluded(depth, camera, front, pixel, body.radius, &vp_matrix) {
+            continue;
+        }
+
+        candidates.push(Candidate {
+            body: i,
+            center: (x as f32, y as f32),
+            radius: body.radius * pixels_per_unit / distance,
+            distance,
+            alpha,
+            color: rgb_f32_to_u32(
+                0.5 + body.color.0 * 0.5,
+                0.5 + body.color.1 * 0.5,
+                0.5 + body.color.2 * 0.5,
+            ),
+        });
+    }
+
+    // Primero los cuerpos más grandes en pantalla, luego los más cercanos
+    candidates.sort_by(|a, b| {
+        b.radius
+            .total_cmp(&a.radius)
+            .then(a.distance.total_cmp(&b.distance))
+    });
+
+    let mut placed: Vec<Rect> = Vec::new();
+    for candidate in &candidates {
+        let body = &solar_system.bodies[candidate.body];
+        if let Some(rect) = place_label(fb, &body.name, candidate, settings, &placed) {
+            placed.push(rect);
+        }
+    }
+}
+
+/// Hay algo en el z-buffer delante del frente del cuerpo
+fn is_occluded(
+    depth: &Framebuffer,
+    camera: &Camera,
+    front: Vec3,
+    pixel: (usize, usize),
+    radius: f32,
+    vp_matrix: &Mat4,
+) -> bool {
+    let stored = depth.zbuffer[depth.get_index(pixel.0, pixel.1)];
+    if !stored.is_finite() {
+        return false;
+    }
+    let front_depth = match project_to_screen(front, vp_matrix, depth.width, depth.height) {
+        Some((_, _, z)) => linear_depth(z, camera.near, camera.far),
+        None => return false, // La cámara está dentro del frente: nada puede taparlo
+    };
+    // La malla facetada queda un poco por dentro de la esfera
+    linear_depth(stored, camera.near, camera.far) < front_depth - (radius * 0.05 + 0.01)
+}
+
+/// Busca una esquina libre alrededor del cuerpo y dibuja la etiqueta
+fn place_label(
+    fb: &mut Framebuffer,
+    name: &str,
+    candidate: &Candidate,
+    settings: &LabelSettings,
+    placed: &[Rect],
+) -> Option<Rect> {
+    let text_width = font::text_width(name, 1) as i32;
+    let text_height = GLYPH_HEIGHT as i32;
+    let (cx, cy) = candidate.center;
+    let edge = candidate.radius * std::f32::consts::FRAC_1_SQRT_2;
+
+    // Arriba a la derecha, arriba a la izquierda, abajo a la derecha, abajo a la izquierda
+    for (sx, sy) in [(1.0, -1.0), (-1.0, -1.0), (1.0, 1.0), (-1.0, 1.0)] {
+        let start = (cx + sx * edge, cy + sy * edge);
+        let elbow = (
+            start.0 + sx * settings.leader_length,
+            start.1 + sy * settings.leader_length,
+        );
+        let text_x = if sx > 0.0 { elbow.0 as i32 + 3 } else { elbow.0 as i32 - 3 - text_width };
+        let text_y = elbow.1 as i32 - text_height / 2;
+        let rect = (text_x - 2, text_y - 2, text_x + text_width + 2, text_y + text_height + 2);
+
+        let on_screen = rect.0 >= 0 && rect.1 >= 0 && rect.2 < fb.width as i32 && rect.3 < fb.height as i32;
+        if !on_screen || placed.iter().any(|other| overlaps(&rect, other)) {
+            continue;
+        }
+
+        blend_line(fb, start, elbow, candidate.color, candidate.alpha * 0.7);
+        font::draw_text_alpha(fb, text_x + 1, text_y + 1, name, 0xFF000000, 1, candidate.alpha * 0.8);
+        font::draw_text_alpha(fb, text_x, text_y, name, candidate.color, 1, candidate.alpha);
+        return Some(rect);
+    }
+    None
+}
+
+fn overlaps(a: &Rect, b: &Rect) -> bool {
+    a.0 < b.2 && b.0 < a.2 && a.1 < b.3 && b.1 < a.3
+}
+
+/// Línea semitransparente
+fn blend_line(fb: &mut Framebuffer, from: (f32, f32), to: (f32, f32), color: u32, alpha: f32) {
+    walk_line((from.0 as i32, from.1 as i32), (to.0 as i32, to.1 as i32), |x, y| {
+        fb.blend_pixel(x as usize, y as usize, color, alpha);
+    });
+}
+
+/// Profundidad en unidades de vista a partir de la z de NDC
+fn linear_depth(ndc_z: f32, near: f32, far: f32) -> f32 {
+    2.0 * near * far / (far + near - ndc_z * (far - near))
+}
@@ -4,10 +4,28 @@ pub mod triangle;
 pub mod shapes;
 pub mod shader;
 pub mod skybox;
+pub mod export;
+pub mod pipeline;
+pub mod clipping;
+pub mod texture;
+pub mod postprocess;
+pub mod font;
+pub mod hud;
+pub mod labels;
+pub mod render_target;
 
-pub use framebuffer::{Framebuffer, rgb_to_u32, rgb_f32_to_u32};
+pub use framebuffer::{Framebuffer, FramebufferBand, rgb_to_u32, rgb_f32_to_u32};
 pub use vertex::Vertex;
-pub use triangle::{rasterize_triangle, draw_line};
-pub use shapes::{generate_sphere, generate_orbit_points};
+pub use triangle::{ShadedTriangle, ShaderUniforms, TransformedVertex, LineVertex, rasterize_with_shader, draw_line_3d, walk_line};
+pub use shapes::generate_sphere;
 pub use shader::*;
-pub use skybox::render_skybox;
+pub use skybox::{SkyBackground, render_skybox};
+pub use export::{ImageFormat, save_frame, save_ppm, save_png};
+pub use pipeline::{Antialiasing, OrbitBackStyle, Pipeline};
+pub use clipping::{ClipVertex, ClipPlane, clip_segment, clip_triangle};
+pub use texture::Texture;
+pub use postprocess::{radial_blur, resample};
+pub use font::{draw_text, draw_text_alpha, draw_text_shadowed, text_width, text_height};
+pub use hud::Hud;
+pub use labels::{LabelSettings, render_labels};
+pub use render_target::{RenderTarget, RENDER_SCALES};
@@ -0,0 +1,496 @@
+use rayon::prelude::*;
+
+use crate::camera::Camera;
+use crate::math::{Vec3, Vec4, Mat4};
+use crate::scene::{CelestialBody, SolarSystem};
+use super::clipping::{ClipVertex, clip_segment, clip_triangle};
+use super::framebuffer::{Framebuffer, rgb_to_u32};
+use super::labels::{LabelSettings, render_labels};
+use super::postprocess::radial_blur;
+use super::render_target::RenderTarget;
+use super::skybox::{SkyBackground, render_skybox};
+use super::triangle::{LineVertex, ShadedTriangle, ShaderUniforms, TransformedVertex, draw_line_3d, rasterize_with_shader};
+use super::vertex::Vertex;
+
+/// Anti-aliasing de los bordes de los cuerpos
+///
+/// MSAA evalúa cobertura y profundidad en varias muestras por píxel pero
+/// sombrea una sola vez, así que cuesta mucho menos que subir la escala de
+/// render (que equivale a SSAA). Con MSAA activo las órbitas se dibujan con
+/// líneas suavizadas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Antialiasing {
+    #[default]
+    None,
+    Msaa2,
+    Msaa4,
+}
+
+impl Antialiasing {
+    /// Modos en el orden en que se alternan
+    pub const ALL: [Antialiasing; 3] = [Antialiasing::None, Antialiasing::Msaa2, Antialiasing::Msaa4];
+
+    /// Muestras por píxel
+    pub fn samples(&self) -> usize {
+        match self {
+            Antialiasing::None => 1,
+            Antialiasing::Msaa2 => 2,
+            Antialiasing::Msaa4 => 4,
+        }
+    }
+
+    /// Nombre usado en la línea de comandos ("none", "msaa2", "msaa4")
+    pub fn name(&self) -> &'static str {
+        match self {
+            Antialiasing::None => "none",
+            Antialiasing::Msaa2 => "msaa2",
+            Antialiasing::Msaa4 => "msaa4",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|mode| mode.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Texto para el HUD
+    pub fn label(&self) -> &'static str {
+        match self {
+            Antialiasing::None => "sin AA",
+            Antialiasing::Msaa2 => "MSAA 2x",
+            Antialiasing::Msaa4 => "MSAA 4x",
+        }
+    }
+
+    /// Siguiente modo (vuelve a `None` después del último)
+    pub fn next(&self) -> Self {
+        let index = Self::ALL.iter().position(|mode| mode == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+}
+
+/// Estilo del tramo de cada órbita que queda detrás de su centro (la estrella
+/// para los planetas, el planeta para las lunas)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OrbitBackStyle {
+    #[default]
+    Solid,
+    Dashed,
+    Faded,
+}
+
+impl OrbitBackStyle {
+    /// Estilos en el orden en que se alternan
+    pub const ALL: [OrbitBackStyle; 3] = [OrbitBackStyle::Solid, OrbitBackStyle::Dashed, OrbitBackStyle::Faded];
+
+    /// Nombre usado en la línea de comandos ("solid", "dashed", "faded")
+    pub fn name(&self) -> &'static str {
+        match self {
+            OrbitBackStyle::Solid => "solid",
+            OrbitBackStyle::Dashed => "dashed",
+            OrbitBackStyle::Faded => "faded",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|style| style.name().eq_ignore_ascii_case(name))
+    }
+
+    /// Texto para el HUD
+    pub fn label(&self) -> &'static str {
+        match self {
+            OrbitBackStyle::Solid => "continuo",
+            OrbitBackStyle::Dashed => "punteado",
+            OrbitBackStyle::Faded => "desvanecido",
+        }
+    }
+
+    /// Siguiente estilo (vuelve a `Solid` después del último)
+    pub fn next(&self) -> Self {
+        let index = Self::ALL.iter().position(|style| style == self).unwrap_or(0);
+        Self::ALL[(index + 1) % Self::ALL.len()]
+    }
+
+    /// Opacidad de un punto de la órbita
+    ///
+    /// `behind` es cuánto más lejos de la cámara que el centro está el punto,
+    /// en fracción del radio de la órbita; `position` avanza de a 1 por segmento.
+    fn opacity(&self, behind: f32, position: f32) -> f32 {
+        if behind <= 0.0 {
+            return 1.0;
+        }
+        match self {
+            OrbitBackStyle::Solid => 1.0,
+            // Dos trazos por segmento, fijos sobre la órbita
+            OrbitBackStyle::Dashed => if (position * 2.0).fract() < 0.5 { 1.0 } else { 0.0 },
+            // Transición suave en el primer 20% del radio
+            OrbitBackStyle::Faded => 1.0 - 0.7 * (behind / 0.2).min(1.0),
+        }
+    }
+}
+
+/// Pipeline de renderizado por software
+///
+/// Dibuja un frame completo del sistema solar: skybox, cuerpos celestes con
+/// sus shaders procedurales y órbitas. Los triángulos se agrupan por
+/// bandas horizontales del framebuffer y cada banda se sombrea en paralelo
+/// (rayon); el resultado es idéntico al del camino serial.
+#[derive(Debug, Clone)]
+pub struct Pipeline {
+    pub draw_skybox: bool,
+    pub sky: SkyBackground,  // Fondo del skybox (procedural o imagen)
+    pub draw_orbits: bool,
+    pub orbit_back: OrbitBackStyle, // Tramo de las órbitas detrás de su centro
+    pub warp_effects: bool,  // Estelas y desenfoque radial durante un warp
+    pub labels: LabelSettings, // Nombres de los cuerpos con línea guía
+    pub antialiasing: Antialiasing,
+    pub parallel: bool,      // Rasterizar bandas en paralelo
+    pub band_height: usize,  // Filas por banda
+}
+
+impl Pipeline {
+    pub fn new() -> Self {
+        Self {
+            draw_skybox: true,
+            sky: SkyBackground::Procedural,
+            draw_orbits: true,
+            orbit_back: OrbitBackStyle::Solid,
+            warp_effects: true,
+            labels: LabelSettings::default(),
+            antialiasing: Antialiasing::None,
+            parallel: true,
+            band_height: 16,
+        }
+    }
+
+    /// Renderiza un frame completo en el framebuffer
+    pub fn render(
+        &self,
+        fb: &mut Framebuffer,
+        solar_system: &SolarSystem,
+        camera: &Camera,
+        time: f32,
+    ) {
+        // Skybox primero (sin depth buffer)
+        if self.draw_skybox {
+            render_skybox(fb, camera, &self.sky, time);
+            fb.clear_depth();
+        } else {
+            fb.clear();
+        }
+
+        // Matriz VP (View-Projection)
+        let vp_matrix = camera.view_projection_matrix();
+
+        self.render_bodies(fb, solar_system, camera, time);
+
+        // Órbitas después de los cuerpos: se ocultan con su z-buffer
+        if self.draw_orbits {
+            self.render_orbits(fb, solar_system, &vp_matrix);
+        }
+
+        if self.warp_effects {
+            self.render_warp_blur(fb, camera, &vp_matrix);
+        }
+    }
+
+    /// Renderiza la escena a la resolución interna de `target`, la resuelve a la
+    /// de salida y dibuja encima las etiquetas de los cuerpos
+    pub fn render_frame(
+        &self,
+        target: &mut RenderTarget,
+        solar_system: &SolarSystem,
+        camera: &Camera,
+        time: f32,
+    ) {
+        self.render(&mut target.scene, solar_system, camera, time);
+        target.resolve();
+        // Las etiquetas usan el z-buffer de la escena para ocultarse
+        render_labels(&mut target.output, &target.scene, solar_system, camera, &self.labels);
+    }
+
+    /// Desenfoque radial hacia el punto de fuga del viaje en curso
+    fn render_warp_blur(&self, fb: &mut Framebuffer, camera: &Camera, vp_matrix: &Mat4) {
+        let effect = match camera.warp_effect() {
+            Some(effect) if effect.intensity > 0.01 => effect,
+            _ => return,
+        };
+        // Punto de fuga: proyección de la dirección de viaje
+        let ahead = vp_matrix.transform_point_clip(&(camera.position + effect.direction));
+        let center = if ahead.w > 0.0 {
+            let ndc = ahead.perspective_divide();
+            (
+                (ndc.x + 1.0) * 0.5 * fb.width as f32,
+                (1.0 - ndc.y) * 0.5 * fb.height as f32,
+            )
+        } else {
+            (fb.width as f32 * 0.5, fb.height as f32 * 0.5)
+        };
+        radial_blur(fb, center, effect.intensity * 0.12);
+    }
+
+    /// Renderiza todos los cuerpos celestes
+    ///
+    /// Primero transforma y descarta triángulos, luego los asigna a las bandas
+    /// que tocan y finalmente rasteriza cada banda respetando el orden de envío.
+    /// Con MSAA las bandas escriben en los buffers de muestras, que se resuelven
+    /// sobre el framebuffer al final.
+    pub fn render_bodies(&self, fb: &mut Framebuffer, solar_system: &SolarSystem, camera: &Camera, time: f32) {
+        let (width, height) = (fb.width, fb.height);
+        let vp_matrix = camera.view_projection_matrix();
+
+        let uniforms: Vec<ShaderUniforms> = solar_system
+            .bodies
+            .iter()
+            .enumerate()
+            .map(|(i, body)| ShaderUniforms {
+                shader_type: body.shader_type,
+                model: solar_system.get_model_matrix(i),
+                camera_position: camera.position,
+                // El sol no necesita luz externa
+                light: if body.is_emissive { None } else { Some(solar_system.light) },
+                time,
+            })
+            .collect();
+
+        let triangles: Vec<ShadedTriangle> = if self.parallel {
+            solar_system
+                .bodies
+                .par_iter()
+                .enumerate()
+                .flat_map_iter(|(i, body)| self.setup_body(body, i, &uniforms[i].model, &vp_matrix, width, height))
+                .collect()
+        } else {
+            solar_system
+                .bodies
+                .iter()
+                .enumerate()
+                .flat_map(|(i, body)| self.setup_body(body, i, &uniforms[i].model, &vp_matrix, width, height))
+                .collect()
+        };
+
+        // Binning: índices de triángulos por banda, en orden de envío
+        let band_height = self.band_height.max(1);
+        let band_count = height.div_ceil(band_height);
+        let mut bins: Vec<Vec<usize>> = vec![Vec::new(); band_count];
+        for (index, triangle) in triangles.iter().enumerate() {
+            let (min_y, max_y) = triangle.row_span();
+            if max_y < 0 || min_y >= height as i32 {
+                continue;
+            }
+            let first = min_y.max(0) as usize / band_height;
+            let last = (max_y as usize).min(height - 1) / band_height;
+            for bin in &mut bins[first..=last] {
+                bin.push(index);
+            }
+        }
+
+        fb.set_samples(self.antialiasing.samples());
+        fb.clear_samples();
+        let bands = fb.bands_mut(band_height);
+        let shade_band = |(mut band, bin): (_, &Vec<usize>)| {
+            for &index in bin {
+                let triangle = &triangles[index];
+                rasterize_with_shader(&mut band, triangle, &uniforms[triangle.uniforms]);
+            }
+        };
+        if self.parallel {
+            bands.into_par_iter().zip(bins.par_iter()).for_each(shade_band);
+        } else {
+            bands.into_iter().zip(bins.iter()).for_each(shade_band);
+        }
+        fb.resolve_samples();
+    }
+
+    /// Transforma los triángulos de un cuerpo y descarta los no visibles
+    ///
+    /// `uniforms` es el índice de los parámetros del shader de este cuerpo.
+    pub fn setup_body(
+        &self,
+        body: &CelestialBody,
+        uniforms: usize,
+        model_matrix: &Mat4,
+        vp_matrix: &Mat4,
+        width: usize,
+        height: usize,
+    ) -> Vec<ShadedTriangle> {
+        let mvp = vp_matrix.multiply(model_matrix);
+
+        let mut triangles = Vec::new();
+        let mut clipped = Vec::new();
+        for triangle in &body.mesh {
+            // Transformar vértices a espacio de clip (sin dividir por w)
+            let clip = [
+                transform_vertex(&triangle[0], &mvp, model_matrix),
+                transform_vertex(&triangle[1], &mvp, model_matrix),
+                transform_vertex(&triangle[2], &mvp, model_matrix),
+            ];
+
+            // Recortar contra el frustum antes de la división de perspectiva
+            clipped.clear();
+            clip_triangle(&clip, &mut clipped);
+
+            for piece in &clipped {
+                let transformed = piece.map(|v| TransformedVertex::from_clip(&v));
+
+                // Back-face culling simple
+                let normal = calculate_face_normal(
+                    &transformed[0].ndc,
+                    &transformed[1].ndc,
+                    &transformed[2].ndc,
+                );
+                if normal.z > 0.0 {
+                    continue; // Cara trasera, no renderizar
+                }
+
+                triangles.push(ShadedTriangle::new(transformed, uniforms, width, height));
+            }
+        }
+        triangles
+    }
+
+    /// Renderiza las órbitas de los planetas
+    ///
+    /// Cada segmento se recorta contra el frustum y se rasteriza en 3D con
+    /// z-test (sin escribir profundidad), así los cuerpos delante las tapan y
+    /// no desaparecen cerca de la cámara. Se suavizan si hay anti-aliasing.
+    pub fn render_orbits(&self, fb: &mut Framebuffer, solar_system: &SolarSystem, vp_matrix: &Mat4) {
+        let smooth = self.antialiasing != Antialiasing::None;
+        for (i, orbit) in solar_system.orbit_points.iter().enumerate() {
+            if orbit.is_empty() {
+                continue;
+            }
+
+            // Color de órbita basado en el planeta
+            let body = &solar_system.bodies[i];
+            // Las órbitas de las lunas se mueven con su planeta
+            let orbit_matrix = vp_matrix.multiply(&solar_system.parent_frame(i));
+            let orbit_color = rgb_to_u32(
+                (body.color.0 * 100.0) as u8,
+                (body.color.1 * 100.0) as u8,
+                (body.color.2 * 100.0) as u8,
+            );
+            // Profundidad de vista del centro (w en espacio de clip)
+            let center_depth = orbit_matrix.transform_point_clip(&Vec3::zero()).w;
+            let radius = body.orbit.radius.max(1e-3);
+
+            for j in 0..orbit.len() {
+                let p1 = orbit_matrix.transform_point_clip(&orbit[j]);
+                let p2 = orbit_matrix.transform_point_clip(&orbit[(j + 1) % orbit.len()]);
+                let (t0, t1) = match clip_segment(&p1, &p2) {
+                    Some(range) => range,
+                    None => continue,
+                };
+
+                let a = LineVertex::from_clip(&Vec4::lerp(&p1, &p2, t0), fb.width, fb.height);
+                let b = LineVertex::from_clip(&Vec4::lerp(&p1, &p2, t1), fb.width, fb.height);
+                draw_line_3d(fb, &a, &b, orbit_color, smooth, |t, depth| {
+                    let position = j as f32 + t0 + (t1 - t0) * t;
+                    self.orbit_back.opacity((depth - center_depth) / radius, position)
+                });
+            }
+        }
+    }
+}
+
+impl Default for Pipeline {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Transforma un vértice a espacio de clip con su normal en espacio mundo
+fn transform_vertex(v: &Vertex, mvp: &Mat4, model: &Mat4) -> ClipVertex {
+    ClipVertex {
+        position: mvp.transform_point_clip(&v.position),
+        normal: model.transform_direction(&v.normal).normalize(),
+        world: model.transform_point(&v.position),
+        local: v.position,
+    }
+}
+
+/// Calcula la normal de una cara (para back-face culling)
+fn calculate_face_normal(v0: &Vec3, v1: &Vec3, v2: &Vec3) -> Vec3 {
+    let edge1 = *v1 - *v0;
+    let edge2 = *v2 - *v0;
+    edge1.cross(&edge2).normalize()
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Renderiza los cuerpos de la escena por defecto con y sin bandas en paralelo
+    fn render_both(antialiasing: Antialiasing, band_height: usize) -> (Framebuffer, Framebuffer) {
+        let mut solar_system = SolarSystem::new();
+        solar_system.update(3.0);
+        // 75 filas: no es múltiplo del alto de banda
+        let (width, height) = (120, 75);
+        let mut camera = Camera::new(width as f32 / height as f32);
+        camera.look_at_target(solar_system.get_body_position(0));
+        camera.set_distance(solar_system.camera_distance);
+
+        let render = |parallel: bool| {
+            let pipeline = Pipeline { antialiasing, parallel, band_height, ..Pipeline::new() };
+            let mut fb = Framebuffer::new(width, height);
+            fb.clear();
+            pipeline.render_bodies(&mut fb, &solar_system, &camera, 1.5);
+            fb
+        };
+        (render(true), render(false))
+    }
+
+    #[test]
+    fn parallel_bands_match_serial() {
+        for antialiasing in Antialiasing::ALL {
+            for band_height in [1, 7, 16, 200] {
+                let (parallel, serial) = render_both(antialiasing, band_height);
+                assert!(serial.zbuffer.iter().any(|z| z.is_finite()), "no se dibujó nada");
+                assert!(parallel.buffer == serial.buffer, "color con {:?}, bandas de {}", antialiasing, band_height);
+                let same_depth = parallel.zbuffer.iter().zip(&serial.zbuffer).all(|(a, b)| a.to_bits() == b.to_bits());
+                assert!(same_depth, "profundidad con {:?}, bandas de {}", antialiasing, band_height);
+            }
+        }
+    }
+
+    #[test]
+    fn orbit_back_style_only_touches_the_far_half() {
+        // Solo las órbitas alrededor del sol, vistas desde arriba y adelante:
+        // la mitad lejana queda por encima del centro en pantalla
+        let mut solar_system = SolarSystem::new();
+        for i in 0..solar_system.body_count() {
+            if solar_system.bodies[i].parent.is_some() {
+                solar_system.orbit_points[i].clear();
+            }
+        }
+        let (width, height) = (200, 150);
+        let mut camera = Camera::new(width as f32 / height as f32);
+        camera.look_at_target(Vec3::zero());
+        camera.set_distance(60.0);
+        let vp_matrix = camera.view_projection_matrix();
+        let center = vp_matrix.transform_point_clip(&Vec3::zero());
+        let center_row = (1.0 - center.y / center.w) * 0.5 * height as f32;
+
+        for antialiasing in [Antialiasing::None, Antialiasing::Msaa4] {
+            let render = |orbit_back: OrbitBackStyle| {
+                let pipeline = Pipeline { orbit_back, antialiasing, ..Pipeline::new() };
+                let mut fb = Framebuffer::new(width, height);
+                fb.clear();
+                pipeline.render_orbits(&mut fb, &solar_system, &vp_matrix);
+                fb
+            };
+            let solid = render(OrbitBackStyle::Solid);
+            assert!(solid.buffer.iter().enumerate().any(|(i, &c)| c != solid.background_color && (i / width) as f32 > center_row + 2.0));
+
+            for style in [OrbitBackStyle::Dashed, OrbitBackStyle::Faded] {
+                let styled = render(style);
+                let changed: Vec<usize> = (0..solid.buffer.len()).filter(|&i| styled.buffer[i] != solid.buffer[i]).collect();
+                assert!(!changed.is_empty(), "{:?} no cambia nada", style);
+                for i in changed {
+                    let row = (i / width) as f32;
+                    assert!(row <= center_row + 1.0, "{:?} cambió la fila {} (centro {})", style, row, center_row);
+                }
+            }
+        }
+    }
+}
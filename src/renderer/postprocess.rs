@@ -0,0 +1,101 @@
+use rayon::prelude::*;
+
+use super::framebuffer::{Framebuffer, rgb_to_u32};
+
+/// Muestras por píxel del desenfoque radial
+const BLUR_SAMPLES: usize = 8;
+
+/// Desenfoque radial (zoom blur) hacia un punto de la pantalla
+///
+/// Cada píxel promedia muestras sobre la recta que lo une con `center`; el
+/// desenfoque crece con la distancia al centro. `strength` es la fracción de
+/// esa distancia que se recorre (0 = sin efecto).
+pub fn radial_blur(fb: &mut Framebuffer, center: (f32, f32), strength: f32) {
+    if strength <= 0.0 {
+        return;
+    }
+    let (width, height) = (fb.width, fb.height);
+    let source = fb.buffer.clone();
+
+    fb.buffer
+        .par_chunks_mut(width)
+        .enumerate()
+        .for_each(|(y, row)| {
+            for (x, pixel) in row.iter_mut().enumerate() {
+                let (dx, dy) = (center.0 - x as f32, center.1 - y as f32);
+                let (mut r, mut g, mut b) = (0u32, 0u32, 0u32);
+                for k in 0..BLUR_SAMPLES {
+                    let s = strength * k as f32 / BLUR_SAMPLES as f32;
+                    let sx = (x as f32 + dx * s).round().clamp(0.0, (width - 1) as f32) as usize;
+                    let sy = (y as f32 + dy * s).round().clamp(0.0, (height - 1) as f32) as usize;
+                    let color = source[sy * width + sx];
+                    r += (color >> 16) & 0xFF;
+                    g += (color >> 8) & 0xFF;
+                    b += color & 0xFF;
+                }
+                let n = BLUR_SAMPLES as u32;
+                *pixel = rgb_to_u32((r / n) as u8, (g / n) as u8, (b / n) as u8);
+            }
+        });
+}
+
+/// Copia `src` en `dst` cambiando la resolución
+///
+/// Al reducir promedia todos los píxeles de origen que cubre cada píxel de
+/// destino (resolve del supersampling); al ampliar interpola bilinealmente.
+pub fn resample(src: &Framebuffer, dst: &mut Framebuffer) {
+    if src.width == dst.width && src.height == dst.height {
+        dst.buffer.copy_from_slice(&src.buffer);
+        return;
+    }
+    let scale_x = src.width as f32 / dst.width as f32;
+    let scale_y = src.height as f32 / dst.height as f32;
+    let downsample = scale_x >= 1.0 && scale_y >= 1.0;
+    let dst_width = dst.width;
+
+    dst.buffer
+        .par_chunks_mut(dst_width)
+        .enumerate()
+        .for_each(|(y, row)| {
+            for (x, pixel) in row.iter_mut().enumerate() {
+                *pixel = if downsample {
+                    box_filter(src, (x as f32 * scale_x, (x + 1) as f32 * scale_x), (y as f32 * scale_y, (y + 1) as f32 * scale_y))
+                } else {
+                    bilinear(src, (x as f32 + 0.5) * scale_x - 0.5, (y as f32 + 0.5) * scale_y - 0.5)
+                };
+            }
+        });
+}
+
+/// Promedio de los píxeles de `src` dentro de [x0, x1) × [y0, y1)
+fn box_filter(src: &Framebuffer, (x0, x1): (f32, f32), (y0, y1): (f32, f32)) -> u32 {
+    let (x_start, x_end) = (x0 as usize, (x1.ceil() as usize).clamp(x0 as usize + 1, src.width));
+    let (y_start, y_end) = (y0 as usize, (y1.ceil() as usize).clamp(y0 as usize + 1, src.height));
+    let (mut r, mut g, mut b) = (0u32, 0u32, 0u32);
+    for sy in y_start..y_end {
+        for &color in &src.buffer[sy * src.width + x_start..sy * src.width + x_end] {
+            r += (color >> 16) & 0xFF;
+            g += (color >> 8) & 0xFF;
+            b += color & 0xFF;
+        }
+    }
+    let n = ((x_end - x_start) * (y_end - y_start)) as u32;
+    rgb_to_u32((r / n) as u8, (g / n) as u8, (b / n) as u8)
+}
+
+/// Muestra bilineal de `src` en coordenadas de píxel (centros en x + 0.5)
+fn bilinear(src: &Framebuffer, x: f32, y: f32) -> u32 {
+    let x = x.clamp(0.0, (src.width - 1) as f32);
+    let y = y.clamp(0.0, (src.height - 1) as f32);
+    let (x0, y0) = (x as usize, y as usize);
+    let (x1, y1) = ((x0 + 1).min(src.width - 1), (y0 + 1).min(src.height - 1));
+    let (fx, fy) = (x - x0 as f32, y - y0 as f32);
+
+    let channel = |shift: u32| {
+        let c = |px: usize, py: usize| ((src.buffer[py * src.width + px] >> shift) & 0xFF) as f32;
+        let top = c(x0, y0) + (c(x1, y0) - c(x0, y0)) * fx;
+        let bottom = c(x0, y1) + (c(x1, y1) - c(x0, y1)) * fx;
+        (top + (bottom - top) * fy).round() as u8
+    };
+    rgb_to_u32(channel(16), channel(8), channel(0))
+}
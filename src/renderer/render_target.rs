@@ -0,0 +1,86 @@
+use super::framebuffer::Framebuffer;
+use super::postprocess::resample;
+
+/// Escalas de render que se recorren desde el teclado
+pub const RENDER_SCALES: [f32; 6] = [0.5, 0.75, 1.0, 1.5, 2.0, 3.0];
+
+/// Destino de un frame: la escena a resolución interna y la imagen final
+///
+/// La escena se renderiza a `render_scale` veces la resolución de salida
+/// (0.5 = la mitad de píxeles por eje, 2.0 = supersampling) y `resolve`
+/// la lleva a la resolución de la ventana. Lo que se dibuja después (HUD,
+/// etiquetas) va directo en `output`, siempre nítido.
+pub struct RenderTarget {
+    pub scene: Framebuffer,  // Resolución interna
+    pub output: Framebuffer, // Resolución de la ventana o de la imagen exportada
+    render_scale: f32,
+}
+
+impl RenderTarget {
+    pub const MIN_SCALE: f32 = 0.25;
+    pub const MAX_SCALE: f32 = 4.0;
+
+    pub fn new(width: usize, height: usize, render_scale: f32) -> Self {
+        let render_scale = render_scale.clamp(Self::MIN_SCALE, Self::MAX_SCALE);
+        let (width, height) = (width.max(1), height.max(1));
+        let (scene_width, scene_height) = scaled_size(width, height, render_scale);
+        Self {
+            scene: Framebuffer::new(scene_width, scene_height),
+            output: Framebuffer::new(width, height),
+            render_scale,
+        }
+    }
+
+    /// Resolución de salida
+    pub fn size(&self) -> (usize, usize) {
+        (self.output.width, self.output.height)
+    }
+
+    /// Relación de aspecto de la salida (para la cámara)
+    pub fn aspect(&self) -> f32 {
+        self.output.width as f32 / self.output.height as f32
+    }
+
+    pub fn render_scale(&self) -> f32 {
+        self.render_scale
+    }
+
+    /// Cambia la resolución de salida; devuelve true si cambió
+    pub fn resize(&mut self, width: usize, height: usize) -> bool {
+        let (width, height) = (width.max(1), height.max(1));
+        if (width, height) == self.size() {
+            return false;
+        }
+        *self = Self::new(width, height, self.render_scale);
+        true
+    }
+
+    /// Cambia la escala de render y realoca la escena
+    pub fn set_render_scale(&mut self, scale: f32) {
+        let (width, height) = self.size();
+        *self = Self::new(width, height, scale);
+    }
+
+    /// Siguiente escala de `RENDER_SCALES` hacia arriba o hacia abajo
+    pub fn step_render_scale(&mut self, up: bool) {
+        let current = self.render_scale;
+        let next = if up {
+            RENDER_SCALES.iter().copied().find(|&s| s > current + 1e-3)
+        } else {
+            RENDER_SCALES.iter().rev().copied().find(|&s| s < current - 1e-3)
+        };
+        if let Some(scale) = next {
+            self.set_render_scale(scale);
+        }
+    }
+
+    /// Lleva la escena a la resolución de salida
+    pub fn resolve(&mut self) {
+        resample(&self.scene, &mut self.output);
+    }
+}
+
+fn scaled_size(width: usize, height: usize, scale: f32) -> (usize, usize) {
+    let scaled = |n: usize| ((n as f32 * scale).round() as usize).max(1);
+    (scaled(width), scaled(height))
+}
@@ -66,17 +66,3 @@ fn sphere_point(theta: f32, phi: f32, radius: f32) -> Vec3 {
         radius * theta.sin() * phi.sin(),
     )
 }
-
-/// Genera puntos para dibujar una órbita circular
-pub fn generate_orbit_points(radius: f32, segments: usize) -> Vec<Vec3> {
-    let mut points = Vec::with_capacity(segments);
-    for i in 0..segments {
-        let angle = 2.0 * std::f32::consts::PI * (i as f32) / (segments as f32);
-        points.push(Vec3::new(
-            radius * angle.cos(),
-            0.0, // En el plano eclíptico
-            radius * angle.sin(),
-        ));
-    }
-    points
-}
\ No newline at end of file
@@ -1,42 +1,150 @@
-use crate::math::Vec3;
+use rayon::prelude::*;
+use std::io;
+use std::path::Path;
+
+use crate::camera::Camera;
+use crate::math::{Vec3, Vec4};
 use super::framebuffer::{Framebuffer, rgb_to_u32};
+use super::texture::Texture;
 
-/// Renderiza un skybox con estrellas en el fondo
-pub fn render_skybox(fb: &mut Framebuffer, time: f32) {
-    let width = fb.width;
-    let height = fb.height;
-    
-    for y in 0..height {
-        for x in 0..width {
-            // Convertir coordenadas de pantalla a dirección de raycast
-            let u = (x as f32 / width as f32) * 2.0 - 1.0;
-            let v = (y as f32 / height as f32) * 2.0 - 1.0;
-            
-            // Dirección del ray (apuntando hacia el cielo)
-            let dir = Vec3::new(u * 2.0, v * 1.5, 1.0).normalize();
-            
-            // Color base del cielo (azul oscuro espacial)
-            let sky_color = get_sky_color(&dir);
-            
-            // Añadir estrellas
-            let star_color = get_star(&dir, time);
-            
-            // Combinar cielo y estrellas
-            let final_r = (sky_color.0 + star_color.0).min(1.0);
-            let final_g = (sky_color.1 + star_color.1).min(1.0);
-            let final_b = (sky_color.2 + star_color.2).min(1.0);
-            
-            let color = rgb_to_u32(
-                (final_r * 255.0) as u8,
-                (final_g * 255.0) as u8,
-                (final_b * 255.0) as u8,
+/// Muestras por píxel de las estelas de warp
+const STREAK_SAMPLES: usize = 16;
+
+/// Fondo del skybox
+#[derive(Debug, Clone, Default)]
+pub enum SkyBackground {
+    /// Cielo y estrellas procedurales
+    #[default]
+    Procedural,
+    /// Panorama equirectangular (360° x 180°)
+    Equirect(Texture),
+    /// Cubemap con las caras +X, -X, +Y, -Y, +Z, -Z
+    Cubemap(Box<[Texture; 6]>),
+}
+
+impl SkyBackground {
+    /// Nombres de archivo de las caras del cubemap, en orden
+    pub const CUBEMAP_FACES: [&'static str; 6] = ["px", "nx", "py", "ny", "pz", "nz"];
+
+    /// Carga un fondo desde disco
+    ///
+    /// Un archivo `.ppm` es un panorama equirectangular; un directorio es un
+    /// cubemap con `px.ppm`, `nx.ppm`, `py.ppm`, `ny.ppm`, `pz.ppm` y `nz.ppm`.
+    pub fn load(path: &Path) -> io::Result<Self> {
+        if !path.is_dir() {
+            return Texture::load(path).map(SkyBackground::Equirect);
+        }
+        let mut faces = Vec::with_capacity(6);
+        for face in Self::CUBEMAP_FACES {
+            faces.push(Texture::load(&path.join(format!("{}.ppm", face)))?);
+        }
+        let faces: [Texture; 6] = faces.try_into().expect("seis caras");
+        Ok(SkyBackground::Cubemap(Box::new(faces)))
+    }
+
+    /// Color con estelas hacia atrás de la dirección de viaje (efecto warp)
+    ///
+    /// Cerca del punto de fuga las estelas son cortas y se alargan hacia los
+    /// bordes, como si las estrellas pasaran a los costados.
+    pub fn streaked_color(&self, dir: &Vec3, motion: &Vec3, length: f32, time: f32) -> (f32, f32, f32) {
+        let mut color = self.color(dir, time);
+        for k in 1..=STREAK_SAMPLES {
+            let s = k as f32 / STREAK_SAMPLES as f32;
+            let sample = self.color(&(*dir + *motion * (length * s)).normalize(), time);
+            let fade = 1.0 - s * 0.7;
+            color = (
+                color.0.max(sample.0 * fade),
+                color.1.max(sample.1 * fade),
+                color.2.max(sample.2 * fade),
             );
-            
-            fb.buffer[y * width + x] = color;
+        }
+        color
+    }
+
+    /// Color del fondo en una dirección del mundo (normalizada)
+    pub fn color(&self, dir: &Vec3, time: f32) -> (f32, f32, f32) {
+        match self {
+            SkyBackground::Procedural => {
+                let sky = get_sky_color(dir);
+                let star = get_star(dir, time);
+                (
+                    (sky.0 + star.0).min(1.0),
+                    (sky.1 + star.1).min(1.0),
+                    (sky.2 + star.2).min(1.0),
+                )
+            }
+            SkyBackground::Equirect(texture) => {
+                // Longitud desde -Z (hacia el frente), latitud desde +Y (arriba)
+                let u = 0.5 + dir.x.atan2(-dir.z) / std::f32::consts::TAU;
+                let v = dir.y.clamp(-1.0, 1.0).acos() / std::f32::consts::PI;
+                texture.sample(u, v, true)
+            }
+            SkyBackground::Cubemap(faces) => {
+                let (face, u, v) = cubemap_face(dir);
+                faces[face].sample(u, v, false)
+            }
         }
     }
 }
 
+/// Renderiza el skybox según la orientación de la cámara
+///
+/// Cada píxel se des-proyecta con la inversa de view-projection, así el fondo
+/// queda fijo en el mundo y gira al mover la cámara. Durante un warp las
+/// estrellas se estiran en estelas.
+pub fn render_skybox(fb: &mut Framebuffer, camera: &Camera, sky: &SkyBackground, time: f32) {
+    let width = fb.width;
+    let height = fb.height;
+    let inverse_vp = match camera.view_projection_matrix().inverse() {
+        Some(m) => m,
+        None => {
+            fb.clear();
+            return;
+        }
+    };
+    let streak = camera
+        .warp_effect()
+        .filter(|effect| effect.intensity > 0.01)
+        .map(|effect| (effect.direction, effect.intensity * 0.2));
+
+    fb.buffer
+        .par_chunks_mut(width)
+        .enumerate()
+        .for_each(|(y, row)| {
+            let ndc_y = 1.0 - (y as f32 + 0.5) / height as f32 * 2.0; // Y invertido
+            for (x, pixel) in row.iter_mut().enumerate() {
+                let ndc_x = (x as f32 + 0.5) / width as f32 * 2.0 - 1.0;
+
+                // Dirección del rayo: del plano cercano al lejano
+                let near = inverse_vp.transform_vec4(&Vec4::new(ndc_x, ndc_y, -1.0, 1.0));
+                let far = inverse_vp.transform_vec4(&Vec4::new(ndc_x, ndc_y, 1.0, 1.0));
+                let dir = (far.perspective_divide() - near.perspective_divide()).normalize();
+
+                let (r, g, b) = match &streak {
+                    Some((motion, length)) => sky.streaked_color(&dir, motion, *length, time),
+                    None => sky.color(&dir, time),
+                };
+                *pixel = rgb_to_u32((r * 255.0) as u8, (g * 255.0) as u8, (b * 255.0) as u8);
+            }
+        });
+}
+
+/// Cara del cubemap y coordenadas (u, v) para una dirección
+fn cubemap_face(dir: &Vec3) -> (usize, f32, f32) {
+    let (ax, ay, az) = (dir.x.abs(), dir.y.abs(), dir.z.abs());
+    // (cara, s, t, eje mayor) con la convención habitual de cubemaps
+    let (face, s, t, major) = if ax >= ay && ax >= az {
+        if dir.x > 0.0 { (0, -dir.z, -dir.y, ax) } else { (1, dir.z, -dir.y, ax) }
+    } else if ay >= az {
+        if dir.y > 0.0 { (2, dir.x, dir.z, ay) } else { (3, dir.x, -dir.z, ay) }
+    } else if dir.z > 0.0 {
+        (4, dir.x, -dir.y, az)
+    } else {
+        (5, -dir.x, -dir.y, az)
+    };
+    (face, (s / major + 1.0) * 0.5, (t / major + 1.0) * 0.5)
+}
+
 /// Calcula el color del cielo basado en la dirección
 fn get_sky_color(dir: &Vec3) -> (f32, f32, f32) {
     // Gradiente vertical: más oscuro arriba, ligeramente más claro abajo
@@ -58,7 +166,7 @@ fn get_sky_color(dir: &Vec3) -> (f32, f32, f32) {
 /// Genera estrellas proceduralmente
 fn get_star(dir: &Vec3, time: f32) -> (f32, f32, f32) {
     // Usar la dirección como coordenadas para generar estrellas
-    let scale = 150.0; // Cuántas estrellas (más alto = más densidad)
+    let scale = 400.0; // Celdas por radián (más alto = estrellas más pequeñas)
     
     let x = dir.x * scale;
     let y = dir.y * scale;
@@ -68,21 +176,22 @@ fn get_star(dir: &Vec3, time: f32) -> (f32, f32, f32) {
     let star_value = star_hash(x, y, z);
     
     // Threshold para determinar si hay una estrella
-    let star_threshold = 0.985; // Ajusta para más/menos estrellas (más alto = menos estrellas)
+    let star_threshold = 0.994; // Ajusta para más/menos estrellas (más alto = menos estrellas)
     
     if star_value > star_threshold {
         // Intensidad de la estrella basada en qué tan por encima del threshold está
-        let intensity = ((star_value - star_threshold) / (1.0 - star_threshold)).powf(0.5);
+        let rarity = (star_value - star_threshold) / (1.0 - star_threshold);
+        let intensity = rarity.powf(0.5);
         
         // Parpadeo sutil
         let twinkle = ((time * 3.0 + star_value * 100.0).sin() * 0.5 + 0.5) * 0.3 + 0.7;
         let final_intensity = intensity * twinkle;
         
         // Color de estrella (blanco con ligero tinte)
-        let star_base = if star_value > 0.995 {
+        let star_base = if rarity > 0.67 {
             // Estrellas brillantes - blanco puro
             (1.0, 1.0, 1.0)
-        } else if star_value > 0.992 {
+        } else if rarity > 0.47 {
             // Estrellas azuladas
             (0.8, 0.9, 1.0)
         } else {
@@ -109,12 +218,8 @@ fn star_hash(x: f32, y: f32, z: f32) -> f32 {
     
     // Hash simple pero efectivo
     let mut h = ix * 127.1 + iy * 311.7 + iz * 758.5453;
-    h = (h.sin() * 43758.5453).fract();
+    h = (h.sin() * 43_758.547).fract();
     
     h
 }
 
-/// Función fract (parte fraccionaria)
-fn fract(x: f32) -> f32 {
-    x - x.floor()
-}
@@ -0,0 +1,182 @@
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Imagen RGB en memoria (valores en [0, 1]), usada por el skybox
+#[derive(Debug, Clone)]
+pub struct Texture {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<(f32, f32, f32)>, // Fila a fila, empezando por arriba
+}
+
+impl Texture {
+    /// Carga una imagen PPM (P6 binario o P3 ASCII)
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let bytes = fs::read(path)?;
+        Self::from_ppm(&bytes).map_err(|message| {
+            io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path.display(), message))
+        })
+    }
+
+    /// Interpreta el contenido de un archivo PPM
+    pub fn from_ppm(bytes: &[u8]) -> Result<Self, String> {
+        // Solo se lee PPM: los formatos comprimidos se nombran para que el
+        // error diga qué convertir
+        let other = if bytes.starts_with(b"\x89PNG") {
+            Some("PNG")
+        } else if bytes.starts_with(&[0xFF, 0xD8]) {
+            Some("JPEG")
+        } else {
+            None
+        };
+        if let Some(format) = other {
+            return Err(format!("las imágenes {} no están soportadas, solo PPM (P6 o P3): hay que convertirla a .ppm", format));
+        }
+
+        let mut reader = PpmReader { bytes, pos: 0 };
+        let magic = reader.token().ok_or("archivo vacío")?;
+        let binary = match magic {
+            "P6" => true,
+            "P3" => false,
+            _ => return Err("formato no soportado, solo PPM (P6 o P3)".to_string()),
+        };
+        let width = reader.number()?;
+        let height = reader.number()?;
+        let max_value = reader.number()?;
+        if width == 0 || height == 0 {
+            return Err("la imagen no tiene píxeles".to_string());
+        }
+        if max_value == 0 || max_value > 65535 {
+            return Err(format!("valor máximo inválido: {}", max_value));
+        }
+
+        // Una cabecera enorme no puede tener datos detrás: sin desbordar
+        let missing_data = || "faltan datos de píxeles".to_string();
+        let count = width
+            .checked_mul(height)
+            .and_then(|n| n.checked_mul(3))
+            .ok_or_else(missing_data)?;
+        let scale = 1.0 / max_value as f32;
+        let samples: Vec<f32> = if binary {
+            // Un único espacio separa la cabecera de los datos
+            let start = reader.pos + 1;
+            let sample_size = if max_value < 256 { 1 } else { 2 };
+            let end = count
+                .checked_mul(sample_size)
+                .and_then(|len| start.checked_add(len))
+                .ok_or_else(missing_data)?;
+            let data = bytes.get(start..end).ok_or_else(missing_data)?;
+            if sample_size == 1 {
+                data.iter().map(|&b| b as f32 * scale).collect()
+            } else {
+                data.chunks_exact(2)
+                    .map(|b| u16::from_be_bytes([b[0], b[1]]) as f32 * scale)
+                    .collect()
+            }
+        } else {
+            (0..count)
+                .map(|_| reader.number().map(|v| v as f32 * scale))
+                .collect::<Result<_, _>>()?
+        };
+
+        let pixels = samples
+            .chunks_exact(3)
+            .map(|c| (c[0].min(1.0), c[1].min(1.0), c[2].min(1.0)))
+            .collect();
+        Ok(Self { width, height, pixels })
+    }
+
+    /// Color de un píxel (coordenadas fuera de rango se recortan al borde)
+    pub fn texel(&self, x: isize, y: isize) -> (f32, f32, f32) {
+        let x = x.clamp(0, self.width as isize - 1) as usize;
+        let y = y.clamp(0, self.height as isize - 1) as usize;
+        self.pixels[y * self.width + x]
+    }
+
+    /// Muestreo bilineal con (u, v) en [0, 1], v = 0 arriba
+    ///
+    /// Con `wrap_u` la coordenada horizontal se repite (panoramas 360°).
+    pub fn sample(&self, u: f32, v: f32, wrap_u: bool) -> (f32, f32, f32) {
+        let x = u * self.width as f32 - 0.5;
+        let y = v * self.height as f32 - 0.5;
+        let (x0, y0) = (x.floor(), y.floor());
+        let (tx, ty) = (x - x0, y - y0);
+        let (x0, y0) = (x0 as isize, y0 as isize);
+
+        let column = |x: isize| {
+            if wrap_u { x.rem_euclid(self.width as isize) } else { x }
+        };
+        let (xa, xb) = (column(x0), column(x0 + 1));
+        let lerp = |a: (f32, f32, f32), b: (f32, f32, f32), t: f32| {
+            (a.0 + (b.0 - a.0) * t, a.1 + (b.1 - a.1) * t, a.2 + (b.2 - a.2) * t)
+        };
+        let top = lerp(self.texel(xa, y0), self.texel(xb, y0), tx);
+        let bottom = lerp(self.texel(xa, y0 + 1), self.texel(xb, y0 + 1), tx);
+        lerp(top, bottom, ty)
+    }
+}
+
+/// Lector de la cabecera PPM (tokens separados por espacios, con comentarios #)
+struct PpmReader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> PpmReader<'a> {
+    fn token(&mut self) -> Option<&'a str> {
+        loop {
+            match self.bytes.get(self.pos)? {
+                b'#' => {
+                    while self.bytes.get(self.pos).is_some_and(|&b| b != b'\n') {
+                        self.pos += 1;
+                    }
+                }
+                b if b.is_ascii_whitespace() => self.pos += 1,
+                _ => break,
+            }
+        }
+        let start = self.pos;
+        while self.bytes.get(self.pos).is_some_and(|b| !b.is_ascii_whitespace()) {
+            self.pos += 1;
+        }
+        std::str::from_utf8(&self.bytes[start..self.pos]).ok()
+    }
+
+    fn number(&mut self) -> Result<usize, String> {
+        let token = self.token().ok_or("archivo PPM incompleto")?;
+        token
+            .parse()
+            .map_err(|_| format!("número inválido en el PPM: '{}'", token))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reads_binary_and_ascii_ppm() {
+        let mut binary = b"P6\n# comentario\n2 1\n255\n".to_vec();
+        binary.extend_from_slice(&[255, 0, 0, 0, 0, 255]);
+        let texture = Texture::from_ppm(&binary).unwrap();
+        assert_eq!((texture.width, texture.height), (2, 1));
+        assert_eq!(texture.pixels, [(1.0, 0.0, 0.0), (0.0, 0.0, 1.0)]);
+
+        let ascii = Texture::from_ppm(b"P3 1 1 4  4 2 0").unwrap();
+        assert_eq!(ascii.pixels, [(1.0, 0.5, 0.0)]);
+    }
+
+    #[test]
+    fn malformed_headers_are_errors() {
+        // Tamaños cuyo producto desborda usize
+        let huge = format!("P6\n{} {}\n255\n", usize::MAX, 2);
+        assert_eq!(Texture::from_ppm(huge.as_bytes()).unwrap_err(), "faltan datos de píxeles");
+        let huge = format!("P6\n{} {}\n65535\n", usize::MAX / 4, 1);
+        assert_eq!(Texture::from_ppm(huge.as_bytes()).unwrap_err(), "faltan datos de píxeles");
+
+        assert!(Texture::from_ppm(b"P6\n2 2\n255\n\x00\x00").is_err());
+        assert!(Texture::from_ppm(b"P6\n0 2\n255\n").is_err());
+        assert!(Texture::from_ppm(b"\x89PNG\r\n\x1a\n").unwrap_err().contains("PNG"));
+    }
+}
@@ -1,111 +1,271 @@
-use crate::math::Vec3;
-use super::framebuffer::{Framebuffer, rgb_f32_to_u32};
-use super::vertex::Vertex;
+use crate::math::{Vec3, Vec4, Mat4};
+use crate::shaders::{ShaderType, FragmentData, LightSample, PointLight, apply_shader};
+use super::clipping::ClipVertex;
+use super::framebuffer::{Framebuffer, FramebufferBand, rgb_to_u32};
 
-/// Rasteriza un triángulo usando interpolación baricéntrica
-pub fn rasterize_triangle(
-    fb: &mut Framebuffer,
-    v0: &Vertex,
-    v1: &Vertex,
-    v2: &Vertex,
-    light_dir: &Vec3,
-    is_emissive: bool, // Para el sol, sin iluminación
-) {
-    // Convertir a coordenadas de pantalla
-    let screen_v0 = to_screen_coords(v0.position, fb.width, fb.height);
-    let screen_v1 = to_screen_coords(v1.position, fb.width, fb.height);
-    let screen_v2 = to_screen_coords(v2.position, fb.width, fb.height);
-
-    // Bounding box
-    let min_x = screen_v0.0.min(screen_v1.0).min(screen_v2.0).max(0) as usize;
-    let max_x = screen_v0.0.max(screen_v1.0).max(screen_v2.0).min(fb.width as i32 - 1) as usize;
-    let min_y = screen_v0.1.min(screen_v1.1).min(screen_v2.1).max(0) as usize;
-    let max_y = screen_v0.1.max(screen_v1.1).max(screen_v2.1).min(fb.height as i32 - 1) as usize;
-
-    // Área del triángulo (para coordenadas baricéntricas)
-    let area = edge_function(screen_v0, screen_v1, screen_v2);
+/// Vértice tras la división de perspectiva, listo para rasterizar
+#[derive(Debug, Clone, Copy)]
+pub struct TransformedVertex {
+    pub ndc: Vec3,    // Coordenadas normalizadas (-1 a 1)
+    pub inv_w: f32,   // 1/w del espacio de clip (para interpolación correcta)
+    pub normal: Vec3, // Normal en espacio mundo
+    pub world: Vec3,  // Posición en espacio mundo
+    pub local: Vec3,  // Posición en espacio local
+}
+
+impl TransformedVertex {
+    /// Aplica la división de perspectiva a un vértice en espacio de clip
+    pub fn from_clip(v: &ClipVertex) -> Self {
+        let inv_w = if v.position.w != 0.0 { 1.0 / v.position.w } else { 1.0 };
+        Self {
+            ndc: v.position.perspective_divide(),
+            inv_w,
+            normal: v.normal,
+            world: v.world,
+            local: v.local,
+        }
+    }
+}
+
+/// Parámetros del shader comunes a todos los triángulos de un cuerpo
+#[derive(Debug, Clone, Copy)]
+pub struct ShaderUniforms {
+    pub shader_type: ShaderType,
+    pub model: Mat4,             // Matriz de modelo del cuerpo
+    pub camera_position: Vec3,   // Posición de la cámara en espacio mundo
+    pub light: Option<PointLight>, // None para cuerpos emisivos
+    pub time: f32,
+}
+
+/// Triángulo listo para rasterizar: vértices transformados, coordenadas de
+/// pantalla (con precisión subpíxel) e índice de los uniforms de su cuerpo
+#[derive(Debug, Clone, Copy)]
+pub struct ShadedTriangle {
+    pub vertices: [TransformedVertex; 3],
+    pub screen: [(f32, f32); 3],
+    pub uniforms: usize,
+}
+
+impl ShadedTriangle {
+    /// Proyecta los vértices a pantalla para un framebuffer de `width` x `height`
+    pub fn new(vertices: [TransformedVertex; 3], uniforms: usize, width: usize, height: usize) -> Self {
+        let screen = [
+            to_screen_position(vertices[0].ndc, width, height),
+            to_screen_position(vertices[1].ndc, width, height),
+            to_screen_position(vertices[2].ndc, width, height),
+        ];
+        Self { vertices, screen, uniforms }
+    }
+
+    /// Rango de filas [min_y, max_y] que cubre el triángulo, sin recortar
+    pub fn row_span(&self) -> (i32, i32) {
+        let s = &self.screen;
+        let min_y = s[0].1.min(s[1].1).min(s[2].1);
+        let max_y = s[0].1.max(s[1].1).max(s[2].1);
+        (min_y.floor() as i32, max_y.floor() as i32)
+    }
+}
+
+/// Posiciones de las muestras dentro del píxel (0 a 1) según la cantidad
+///
+/// 4 muestras usan una rejilla rotada: ninguna fila ni columna se repite, lo
+/// que suaviza mejor los bordes casi horizontales o verticales.
+pub fn sample_pattern(samples: usize) -> &'static [(f32, f32)] {
+    match samples {
+        2 => &[(0.25, 0.25), (0.75, 0.75)],
+        4 => &[(0.375, 0.125), (0.875, 0.375), (0.125, 0.625), (0.625, 0.875)],
+        _ => &[(0.5, 0.5)],
+    }
+}
+
+/// Rasteriza un triángulo aplicando shaders por fragmento
+///
+/// Solo escribe las filas que pertenecen a la banda; rasterizar el mismo
+/// triángulo en todas las bandas produce el mismo resultado que hacerlo de una vez.
+///
+/// Con MSAA (`target.samples > 1`) la cobertura y la profundidad se evalúan
+/// en cada muestra, pero el shader corre una sola vez por píxel, en el centro
+/// de las muestras cubiertas.
+pub fn rasterize_with_shader(target: &mut FramebufferBand, triangle: &ShadedTriangle, uniforms: &ShaderUniforms) {
+    let screen = &triangle.screen;
+    if target.y_start >= target.y_end {
+        return;
+    }
+
+    // Bounding box (recortado a la banda)
+    let min_x = (screen[0].0.min(screen[1].0).min(screen[2].0).floor() as i32).max(0);
+    let max_x = (screen[0].0.max(screen[1].0).max(screen[2].0).floor() as i32).min(target.width as i32 - 1);
+    let min_y = (screen[0].1.min(screen[1].1).min(screen[2].1).floor() as i32).max(target.y_start as i32);
+    let max_y = (screen[0].1.max(screen[1].1).max(screen[2].1).floor() as i32).min(target.y_end as i32 - 1);
+    if min_x > max_x || min_y > max_y {
+        return;
+    }
+
+    // Área del triángulo
+    let area = edge_function(screen[0], screen[1], screen[2]);
     if area.abs() < 0.001 {
         return; // Triángulo degenerado
     }
 
-    for y in min_y..=max_y {
-        for x in min_x..=max_x {
-            let p = (x as i32, y as i32);
-            
-            // Coordenadas baricéntricas
-            let w0 = edge_function(screen_v1, screen_v2, p);
-            let w1 = edge_function(screen_v2, screen_v0, p);
-            let w2 = edge_function(screen_v0, screen_v1, p);
-
-            // Verificar si el punto está dentro del triángulo
-            if (w0 >= 0.0 && w1 >= 0.0 && w2 >= 0.0) || (w0 <= 0.0 && w1 <= 0.0 && w2 <= 0.0) {
-                let w0 = w0 / area;
-                let w1 = w1 / area;
-                let w2 = w2 / area;
-
-                // Interpolar Z para depth test
-                let z = v0.position.z * w0 + v1.position.z * w1 + v2.position.z * w2;
-
-                // Interpolar normal
-                let normal = Vec3::new(
-                    v0.normal.x * w0 + v1.normal.x * w1 + v2.normal.x * w2,
-                    v0.normal.y * w0 + v1.normal.y * w1 + v2.normal.y * w2,
-                    v0.normal.z * w0 + v1.normal.z * w1 + v2.normal.z * w2,
-                ).normalize();
-
-                // Interpolar color base
-                let base_color = (
-                    v0.color.0 * w0 + v1.color.0 * w1 + v2.color.0 * w2,
-                    v0.color.1 * w0 + v1.color.1 * w1 + v2.color.1 * w2,
-                    v0.color.2 * w0 + v1.color.2 * w1 + v2.color.2 * w2,
-                );
-
-                // Calcular iluminación
-                let final_color = if is_emissive {
-                    // El sol brilla sin iluminación externa
-                    base_color
-                } else {
-                    // Iluminación difusa simple
-                    let diffuse = normal.dot(light_dir).max(0.1); // 0.1 = luz ambiente
-                    (
-                        base_color.0 * diffuse,
-                        base_color.1 * diffuse,
-                        base_color.2 * diffuse,
-                    )
-                };
-
-                let color = rgb_f32_to_u32(final_color.0, final_color.1, final_color.2);
-                fb.set_pixel(x, y, z, color);
+    let pattern = sample_pattern(target.samples);
+    let mut depths = [0.0f32; 4];
+
+    for y in min_y as usize..=max_y as usize {
+        for x in min_x as usize..=max_x as usize {
+            if target.samples <= 1 {
+                let p = (x as f32 + 0.5, y as f32 + 0.5);
+                if let Some(weights) = barycentric(screen, area, p) {
+                    let z = interpolate_depth(triangle, weights);
+                    let color = shade(triangle, uniforms, weights);
+                    target.set_pixel(x, y, z, color);
+                }
+                continue;
+            }
+
+            // Cobertura y z-test por muestra
+            let mut covered = 0u32;
+            let (mut cx, mut cy, mut count) = (0.0, 0.0, 0.0);
+            for (sample, &(ox, oy)) in pattern.iter().enumerate() {
+                let p = (x as f32 + ox, y as f32 + oy);
+                if let Some(weights) = barycentric(screen, area, p) {
+                    let z = interpolate_depth(triangle, weights);
+                    if z < target.sample_depth(x, y, sample) {
+                        covered |= 1 << sample;
+                        depths[sample] = z;
+                        cx += p.0;
+                        cy += p.1;
+                        count += 1.0;
+                    }
+                }
+            }
+            if covered == 0 {
+                continue;
+            }
+
+            // Un solo shader por píxel, en el centro de las muestras cubiertas
+            // (siempre dentro del triángulo)
+            let weights = barycentric_unchecked(screen, area, (cx / count, cy / count));
+            let color = shade(triangle, uniforms, weights);
+            for (sample, &z) in depths.iter().enumerate().take(pattern.len()) {
+                if covered & (1 << sample) != 0 {
+                    target.set_sample(x, y, sample, z, color);
+                }
             }
         }
     }
 }
 
-/// Convierte coordenadas normalizadas (-1 a 1) a coordenadas de pantalla
-fn to_screen_coords(pos: Vec3, width: usize, height: usize) -> (i32, i32) {
-    let x = ((pos.x + 1.0) * 0.5 * width as f32) as i32;
-    let y = ((1.0 - pos.y) * 0.5 * height as f32) as i32; // Y invertido
+/// Pesos baricéntricos de `p`, o `None` si está fuera del triángulo
+fn barycentric(screen: &[(f32, f32); 3], area: f32, p: (f32, f32)) -> Option<(f32, f32, f32)> {
+    let w0 = edge_function(screen[1], screen[2], p);
+    let w1 = edge_function(screen[2], screen[0], p);
+    let w2 = edge_function(screen[0], screen[1], p);
+    if (w0 >= 0.0 && w1 >= 0.0 && w2 >= 0.0) || (w0 <= 0.0 && w1 <= 0.0 && w2 <= 0.0) {
+        Some((w0 / area, w1 / area, w2 / area))
+    } else {
+        None
+    }
+}
+
+fn barycentric_unchecked(screen: &[(f32, f32); 3], area: f32, p: (f32, f32)) -> (f32, f32, f32) {
+    (
+        edge_function(screen[1], screen[2], p) / area,
+        edge_function(screen[2], screen[0], p) / area,
+        edge_function(screen[0], screen[1], p) / area,
+    )
+}
+
+/// Z en NDC es afín en pantalla: interpolación lineal
+fn interpolate_depth(triangle: &ShadedTriangle, (w0, w1, w2): (f32, f32, f32)) -> f32 {
+    let v = &triangle.vertices;
+    v[0].ndc.z * w0 + v[1].ndc.z * w1 + v[2].ndc.z * w2
+}
+
+/// Pesos con corrección de perspectiva (atributo/w interpolado y dividido por 1/w)
+fn perspective_weights(triangle: &ShadedTriangle, (w0, w1, w2): (f32, f32, f32)) -> (f32, f32, f32) {
+    let v = &triangle.vertices;
+    let p0 = w0 * v[0].inv_w;
+    let p1 = w1 * v[1].inv_w;
+    let p2 = w2 * v[2].inv_w;
+    let inv_sum = 1.0 / (p0 + p1 + p2);
+    (p0 * inv_sum, p1 * inv_sum, p2 * inv_sum)
+}
+
+/// Evalúa el shader del cuerpo en el punto con pesos baricéntricos dados
+fn shade(triangle: &ShadedTriangle, uniforms: &ShaderUniforms, weights: (f32, f32, f32)) -> u32 {
+    let transformed = &triangle.vertices;
+    let (p0, p1, p2) = perspective_weights(triangle, weights);
+
+    // Interpolar normal
+    let normal = (transformed[0].normal * p0
+        + transformed[1].normal * p1
+        + transformed[2].normal * p2)
+        .normalize();
+
+    // Interpolar posiciones local (para el patrón) y mundo (para la luz)
+    let local_pos = transformed[0].local * p0
+        + transformed[1].local * p1
+        + transformed[2].local * p2;
+    let world_pos = transformed[0].world * p0
+        + transformed[1].world * p1
+        + transformed[2].world * p2;
+
+    // Normalizar posición para shaders (esperan posiciones en esfera unitaria)
+    let normalized_pos = local_pos.normalize();
+
+    // Crear datos del fragmento
+    let fragment = FragmentData {
+        position: normalized_pos,
+        normal,
+        world_pos,
+        view_dir: (uniforms.camera_position - world_pos).normalize(),
+        model: uniforms.model,
+    };
+
+    // Luz de la estrella evaluada en este fragmento
+    let light = match &uniforms.light {
+        Some(light) => light.illuminate(world_pos),
+        None => LightSample::none(),
+    };
+
+    // Aplicar shader
+    let color = apply_shader(uniforms.shader_type, &fragment, uniforms.time, &light);
+    rgb_to_u32(
+        (color.0.clamp(0.0, 1.0) * 255.0) as u8,
+        (color.1.clamp(0.0, 1.0) * 255.0) as u8,
+        (color.2.clamp(0.0, 1.0) * 255.0) as u8,
+    )
+}
+
+/// Convierte coordenadas normalizadas (-1 a 1) a coordenadas de pantalla,
+/// sin redondear (precisión subpíxel)
+pub fn to_screen_position(pos: Vec3, width: usize, height: usize) -> (f32, f32) {
+    let x = (pos.x + 1.0) * 0.5 * width as f32;
+    let y = (1.0 - pos.y) * 0.5 * height as f32; // Y invertido
     (x, y)
 }
 
 /// Función de borde para coordenadas baricéntricas
-fn edge_function(v0: (i32, i32), v1: (i32, i32), p: (i32, i32)) -> f32 {
-    ((p.0 - v0.0) * (v1.1 - v0.1) - (p.1 - v0.1) * (v1.0 - v0.0)) as f32
+fn edge_function(v0: (f32, f32), v1: (f32, f32), p: (f32, f32)) -> f32 {
+    (p.0 - v0.0) * (v1.1 - v0.1) - (p.1 - v0.1) * (v1.0 - v0.0)
 }
 
-/// Dibuja una línea (para las órbitas)
-pub fn draw_line(fb: &mut Framebuffer, x0: i32, y0: i32, x1: i32, y1: i32, color: u32) {
-    let dx = (x1 - x0).abs();
-    let dy = -(y1 - y0).abs();
-    let sx = if x0 < x1 { 1 } else { -1 };
-    let sy = if y0 < y1 { 1 } else { -1 };
+/// Recorre los píxeles de una línea (Bresenham), extremos incluidos
+///
+/// `plot` recibe solo coordenadas no negativas; el límite superior lo
+/// comprueba quien dibuja.
+pub fn walk_line(from: (i32, i32), to: (i32, i32), mut plot: impl FnMut(i32, i32)) {
+    let (mut x, mut y) = from;
+    let (x1, y1) = to;
+    let dx = (x1 - x).abs();
+    let dy = -(y1 - y).abs();
+    let sx = if x < x1 { 1 } else { -1 };
+    let sy = if y < y1 { 1 } else { -1 };
     let mut err = dx + dy;
-    let mut x = x0;
-    let mut y = y0;
 
     loop {
-        if x >= 0 && x < fb.width as i32 && y >= 0 && y < fb.height as i32 {
-            fb.set_pixel_no_depth(x as usize, y as usize, color);
+        if x >= 0 && y >= 0 {
+            plot(x, y);
         }
         if x == x1 && y == y1 {
             break;
@@ -120,4 +280,264 @@ pub fn draw_line(fb: &mut Framebuffer, x0: i32, y0: i32, x1: i32, y1: i32, color
             y += sy;
         }
     }
-}
\ No newline at end of file
+}
+
+/// Extremo de un segmento 3D ya recortado contra el frustum
+#[derive(Debug, Clone, Copy)]
+pub struct LineVertex {
+    pub screen: (f32, f32), // Coordenadas de pantalla (subpíxel)
+    pub z: f32,             // Z en NDC, para el z-test
+    pub inv_w: f32,         // 1/w, para interpolar con corrección de perspectiva
+}
+
+impl LineVertex {
+    /// Proyecta un punto en espacio de clip (con w > 0) a pantalla
+    pub fn from_clip(position: &Vec4, width: usize, height: usize) -> Self {
+        let ndc = position.perspective_divide();
+        Self {
+            screen: to_screen_position(ndc, width, height),
+            z: ndc.z,
+            inv_w: 1.0 / position.w,
+        }
+    }
+}
+
+/// Dibuja un segmento 3D con z-test contra el z-buffer, sin escribirlo
+///
+/// `opacity(t, depth)` recibe la posición sobre el segmento (0 en `a`, 1 en
+/// `b`, con corrección de perspectiva) y la profundidad de vista del punto, y
+/// devuelve su opacidad (0 lo omite); así se arman trazos punteados o
+/// desvanecidos. Con `smooth` cada paso reparte el color entre los dos píxeles
+/// que rodean a la línea (algoritmo de Wu).
+pub fn draw_line_3d(
+    fb: &mut Framebuffer,
+    a: &LineVertex,
+    b: &LineVertex,
+    color: u32,
+    smooth: bool,
+    opacity: impl Fn(f32, f32) -> f32,
+) {
+    let (mut x0, mut y0) = a.screen;
+    let (mut x1, mut y1) = b.screen;
+    let steep = (y1 - y0).abs() > (x1 - x0).abs();
+    if steep {
+        std::mem::swap(&mut x0, &mut y0);
+        std::mem::swap(&mut x1, &mut y1);
+    }
+    let reversed = x0 > x1;
+    if reversed {
+        std::mem::swap(&mut x0, &mut x1);
+        std::mem::swap(&mut y0, &mut y1);
+    }
+
+    // Los centros de píxel están en .5
+    let (x0, y0, x1, y1) = (x0 - 0.5, y0 - 0.5, x1 - 0.5, y1 - 0.5);
+    let dx = x1 - x0;
+    let gradient = if dx.abs() < 1e-6 { 0.0 } else { (y1 - y0) / dx };
+
+    // Solo recorrer la parte del eje mayor que cae en pantalla
+    let limit = (if steep { fb.height } else { fb.width }) as f32;
+    let mut plot = |major: i32, minor: i32, z: f32, alpha: f32| {
+        let (x, y) = if steep { (minor, major) } else { (major, minor) };
+        if x >= 0 && y >= 0 && alpha > 0.0 {
+            fb.blend_pixel_tested(x as usize, y as usize, z, color, alpha);
+        }
+    };
+
+    // Extremos: cubren solo la fracción del píxel que ocupa el segmento
+    let start = x0.round();
+    let end = x1.round();
+    let first_cover = 1.0 - (x0 + 0.5 - start);
+    let last_cover = x1 + 0.5 - end;
+
+    let first = start.max(-1.0) as i32;
+    let last = end.min(limit) as i32;
+    for major in first..=last {
+        // Parámetro en pantalla (lineal para z de NDC y 1/w) y en el segmento
+        let along = if dx.abs() < 1e-6 { 0.0 } else { ((major as f32 - x0) / dx).clamp(0.0, 1.0) };
+        let along = if reversed { 1.0 - along } else { along };
+        let z = a.z + (b.z - a.z) * along;
+        let inv_w = a.inv_w + (b.inv_w - a.inv_w) * along;
+        let alpha = opacity(along * b.inv_w / inv_w, 1.0 / inv_w);
+        if alpha <= 0.0 {
+            continue;
+        }
+
+        let y = y0 + gradient * (major as f32 - x0);
+        if !smooth {
+            // El píxel de `b` lo dibuja el segmento siguiente: sin doble mezcla
+            let b_pixel = (if reversed { start } else { end }) as i32;
+            if major != b_pixel {
+                plot(major, y.round() as i32, z, alpha);
+            }
+            continue;
+        }
+        let span = if major == start as i32 && major == end as i32 {
+            (x1 - x0).clamp(0.0, 1.0)
+        } else if major == start as i32 {
+            first_cover
+        } else if major == end as i32 {
+            last_cover
+        } else {
+            1.0
+        };
+        let base = y.floor();
+        let fraction = y - base;
+        plot(major, base as i32, z, (1.0 - fraction) * span * alpha);
+        plot(major, base as i32 + 1, z, fraction * span * alpha);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Vértice con posición de clip `(x, y, 0, w)` y atributos iguales a `world`
+    fn vertex(world: Vec3, w: f32) -> TransformedVertex {
+        TransformedVertex::from_clip(&ClipVertex {
+            position: Vec4::new(world.x, world.y, 0.0, w),
+            normal: Vec3::new(0.0, 0.0, 1.0),
+            world,
+            local: world,
+        })
+    }
+
+    fn uniforms() -> ShaderUniforms {
+        ShaderUniforms {
+            shader_type: ShaderType::Rocky,
+            model: Mat4::identity(),
+            camera_position: Vec3::new(0.0, 0.0, 5.0),
+            light: None,
+            time: 0.0,
+        }
+    }
+
+    /// Rasteriza un triángulo en todo el framebuffer (una sola banda)
+    fn rasterize(fb: &mut Framebuffer, triangle: &ShadedTriangle) {
+        let rows = fb.height;
+        for mut band in fb.bands_mut(rows) {
+            rasterize_with_shader(&mut band, triangle, &uniforms());
+        }
+    }
+
+    #[test]
+    fn attributes_are_perspective_correct() {
+        // Mitad de un quad inclinado: el borde izquierdo a profundidad 1 y el
+        // derecho a profundidad 4 (w = profundidad)
+        let a = Vec3::new(-1.0, -0.5, 1.0);
+        let b = Vec3::new(4.0, -2.0, 4.0);
+        let c = Vec3::new(4.0, 2.0, 4.0);
+        let (width, height) = (64, 32);
+        let triangle = ShadedTriangle::new([vertex(a, a.z), vertex(b, b.z), vertex(c, c.z)], 0, width, height);
+
+        // El centro de la pantalla cae en la mitad de la diagonal a-c en
+        // pantalla, pero en el mundo está a 1/5 del camino: (0, 0, 1.6)
+        let area = edge_function(triangle.screen[0], triangle.screen[1], triangle.screen[2]);
+        let center = (width as f32 / 2.0, height as f32 / 2.0);
+        let weights = barycentric(&triangle.screen, area, center).expect("el centro está en el triángulo");
+        let (p0, p1, p2) = perspective_weights(&triangle, weights);
+        let world = a * p0 + b * p1 + c * p2;
+        assert!(world.x.abs() < 1e-4 && world.y.abs() < 1e-4, "{:?}", world);
+        assert!((world.z - 1.6).abs() < 1e-4, "{:?}", world);
+
+        // La interpolación afín daría el punto medio en el mundo
+        let (w0, w1, w2) = weights;
+        let affine = a * w0 + b * w1 + c * w2;
+        assert!((affine.x - 1.5).abs() < 1e-4, "{:?}", affine);
+    }
+
+    #[test]
+    fn lines_are_hidden_behind_the_z_buffer() {
+        for smooth in [false, true] {
+            // Triángulo a z = 0 que tapa el centro de la fila del medio
+            let (width, height) = (32, 17);
+            let mut fb = Framebuffer::new(width, height);
+            fb.clear();
+            let occluder = [
+                vertex(Vec3::new(-0.3, -1.0, 0.0), 1.0),
+                vertex(Vec3::new(0.3, -1.0, 0.0), 1.0),
+                vertex(Vec3::new(0.0, 3.0, 0.0), 1.0),
+            ];
+            rasterize(&mut fb, &ShadedTriangle::new(occluder, 0, width, height));
+            let before = fb.buffer.clone();
+            let row = |fb: &Framebuffer| 8 * fb.width..9 * fb.width;
+            let occluded: Vec<usize> = row(&fb).filter(|&i| fb.zbuffer[i].is_finite()).collect();
+            assert!(occluded.len() > 4 && occluded.len() < 20);
+
+            // Segmento detrás del triángulo: solo cambia fuera de él
+            let line = |z: f32| {
+                (
+                    LineVertex { screen: (1.5, 8.5), z, inv_w: 1.0 },
+                    LineVertex { screen: (30.5, 8.5), z, inv_w: 1.0 },
+                )
+            };
+            let (a, b) = line(0.5);
+            draw_line_3d(&mut fb, &a, &b, 0xFFFFFF, smooth, |_, _| 1.0);
+            for i in row(&fb) {
+                if occluded.contains(&i) {
+                    assert_eq!(fb.buffer[i], before[i], "píxel tapado {} (smooth = {})", i, smooth);
+                } else if (2..30).contains(&(i % width)) {
+                    assert_ne!(fb.buffer[i], before[i], "píxel visible {} (smooth = {})", i, smooth);
+                }
+            }
+            // La línea no escribe profundidad
+            assert!(row(&fb).all(|i| occluded.contains(&i) || fb.zbuffer[i].is_infinite()));
+
+            // Delante del triángulo sí se dibuja encima
+            let (a, b) = line(-0.5);
+            draw_line_3d(&mut fb, &a, &b, 0xFFFFFF, smooth, |_, _| 1.0);
+            assert!(occluded.iter().all(|&i| fb.buffer[i] & 0xFFFFFF == 0xFFFFFF));
+        }
+    }
+
+    #[test]
+    fn msaa_blends_edges_and_shades_interiors_once() {
+        // Cubre todo lo que está a la izquierda de x = 8.5 en pantalla: la
+        // columna 8 queda con 2 de las 4 muestras dentro
+        let (width, height) = (16, 16);
+        let ndc = |x: f32, y: f32| vertex(Vec3::new(x / 8.0 - 1.0, 1.0 - y / 8.0, 0.0), 1.0);
+        let triangle = ShadedTriangle::new([ndc(-20.0, -20.0), ndc(8.5, -20.0), ndc(8.5, 40.0)], 0, width, height);
+
+        let mut reference = Framebuffer::new(width, height);
+        reference.clear();
+        rasterize(&mut reference, &triangle);
+
+        let mut fb = Framebuffer::new(width, height);
+        fb.clear();
+        fb.set_samples(4);
+        fb.clear_samples();
+        rasterize(&mut fb, &triangle);
+        let samples = fb.sample_color.clone();
+        let sample_depth = fb.sample_depth.clone();
+        fb.resolve_samples();
+
+        let background = fb.background_color;
+        let channel = |color: u32, shift: u32| (color >> shift) & 0xFF;
+        for y in 0..height {
+            // Interior: las cuatro muestras con el mismo color, el del centro
+            // del píxel, igual que sin MSAA
+            let interior = fb.get_index(4, y);
+            let colors = &samples[interior * 4..interior * 4 + 4];
+            assert!(colors.iter().all(|&c| c == colors[0]));
+            assert_eq!(fb.buffer[interior], reference.buffer[interior]);
+
+            // Borde: mitad cubierta, mitad fondo
+            let edge = fb.get_index(8, y);
+            let covered: Vec<usize> = (0..4).filter(|&s| sample_depth[edge * 4 + s].is_finite()).collect();
+            assert_eq!(covered, [0, 2]);
+            let color = samples[edge * 4];
+            for shift in [16, 8, 0] {
+                let expected = (2 * channel(color, shift) + 2 * channel(background, shift)) / 4;
+                assert_eq!(channel(fb.buffer[edge], shift), expected);
+            }
+            assert_ne!(fb.buffer[edge], color);
+            assert_ne!(fb.buffer[edge], background);
+            assert_eq!(fb.zbuffer[edge], 0.0);
+
+            // Fuera del triángulo no se toca nada
+            let outside = fb.get_index(12, y);
+            assert_eq!(fb.buffer[outside], background);
+            assert!(fb.zbuffer[outside].is_infinite());
+        }
+    }
+}
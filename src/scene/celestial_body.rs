@@ -2,23 +2,28 @@ use crate::math::{Vec3, Mat4};
 use crate::renderer::{Vertex, generate_sphere};
 
 use crate::shaders::ShaderType;
+use super::orbit::Orbit;
 
 /// Representa un cuerpo celeste (sol, planeta, luna)
 pub struct CelestialBody {
     pub name: String,
     pub radius: f32,
+    pub mass: f32,              // Masa para el modo de física (gravedad)
     pub color: (f32, f32, f32),
-    pub orbit_radius: f32,      // Distancia al centro de órbita
-    pub orbit_speed: f32,       // Velocidad orbital (radianes/segundo)
+    pub orbit: Orbit,           // Órbita alrededor del padre (radio 0 = estático)
+    pub parent: Option<usize>,  // Índice del cuerpo padre (None = origen del mundo)
+    pub show_orbit: bool,       // Dibujar la línea de la órbita
+    pub show_label: bool,       // Dibujar el nombre en pantalla
     pub rotation_speed: f32,    // Velocidad de rotación propia
-    pub orbit_angle: f32,       // Ángulo actual en la órbita
     pub rotation_angle: f32,    // Ángulo de rotación propia
     pub is_emissive: bool,      // True para el sol (brilla por sí mismo)
     pub shader_type: ShaderType, // Tipo de shader para este cuerpo
+    pub detail: usize,          // Segmentos de la esfera (anillos y sectores)
     pub mesh: Vec<[Vertex; 3]>, // Triángulos de la esfera
 }
 
 impl CelestialBody {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         name: &str,
         radius: f32,
@@ -29,62 +34,83 @@ impl CelestialBody {
         is_emissive: bool,
         shader_type: ShaderType,
     ) -> Self {
-        // Más segmentos para planetas grandes, menos para pequeños
-        let detail = if radius > 1.5 { 24 } else { 16 };
+        let detail = Self::default_detail(radius);
         let mesh = generate_sphere(radius, detail, detail, color);
 
         Self {
             name: name.to_string(),
             radius,
+            mass: radius * radius * radius, // Densidad uniforme por defecto
             color,
-            orbit_radius,
-            orbit_speed,
+            orbit: Orbit::circular(orbit_radius, orbit_speed),
+            parent: None,
+            show_orbit: true,
+            show_label: true,
             rotation_speed,
-            orbit_angle: 0.0,
             rotation_angle: 0.0,
             is_emissive,
             shader_type,
+            detail,
             mesh,
         }
     }
 
+    /// Cambia la cantidad de segmentos de la esfera y regenera la malla
+    pub fn with_detail(mut self, detail: usize) -> Self {
+        self.detail = detail;
+        self.mesh = generate_sphere(self.radius, detail, detail, self.color);
+        self
+    }
+
+    /// Reemplaza la órbita circular por otra (elíptica, inclinada, etc.)
+    pub fn with_orbit(mut self, orbit: Orbit) -> Self {
+        self.orbit = orbit;
+        self
+    }
+
+    /// Hace que el cuerpo orbite alrededor de otro (luna, planeta binario)
+    ///
+    /// El padre debe aparecer antes que el hijo en `SolarSystem::bodies`.
+    pub fn with_parent(mut self, parent: usize) -> Self {
+        self.parent = Some(parent);
+        self
+    }
+
+    /// Segmentos por defecto según el tamaño del cuerpo
+    pub fn default_detail(radius: f32) -> usize {
+        // Más segmentos para planetas grandes, menos para pequeños
+        if radius > 1.5 { 24 } else { 16 }
+    }
+
     /// Actualiza la posición orbital y rotación
     pub fn update(&mut self, delta_time: f32) {
-        self.orbit_angle += self.orbit_speed * delta_time;
+        self.orbit.update(delta_time);
+        self.update_rotation(delta_time);
+    }
+
+    /// Actualiza solo la rotación propia (la posición la da la física)
+    pub fn update_rotation(&mut self, delta_time: f32) {
         self.rotation_angle += self.rotation_speed * delta_time;
 
-        // Mantener ángulos en rango [0, TAU) usando módulo
+        // Mantener el ángulo en rango [0, TAU), también si es negativo
         // Esto previene overflow cuando delta_time es muy grande
-        self.orbit_angle = self.orbit_angle % std::f32::consts::TAU;
-        self.rotation_angle = self.rotation_angle % std::f32::consts::TAU;
-
-        // Manejar ángulos negativos (aunque no debería ocurrir en este caso)
-        if self.orbit_angle < 0.0 {
-            self.orbit_angle += std::f32::consts::TAU;
-        }
-        if self.rotation_angle < 0.0 {
-            self.rotation_angle += std::f32::consts::TAU;
-        }
+        self.rotation_angle = self.rotation_angle.rem_euclid(std::f32::consts::TAU);
     }
 
-    /// Obtiene la posición actual en el espacio
+    /// Obtiene la posición actual relativa al padre (o al origen)
     pub fn get_position(&self) -> Vec3 {
-        if self.orbit_radius == 0.0 {
-            Vec3::zero() // El sol está en el centro
-        } else {
-            Vec3::new(
-                self.orbit_radius * self.orbit_angle.cos(),
-                0.0, // En el plano eclíptico
-                self.orbit_radius * self.orbit_angle.sin(),
-            )
-        }
+        self.orbit.get_position()
     }
 
-    /// Obtiene la matriz de modelo (traslación + rotación)
-    pub fn get_model_matrix(&self) -> Mat4 {
+    /// Traslación orbital relativa al padre (sin la rotación propia)
+    pub fn get_orbit_matrix(&self) -> Mat4 {
         let position = self.get_position();
-        let translation = Mat4::translation(position.x, position.y, position.z);
+        Mat4::translation(position.x, position.y, position.z)
+    }
+
+    /// Obtiene la matriz de modelo relativa al padre (traslación + rotación)
+    pub fn get_model_matrix(&self) -> Mat4 {
         let rotation = Mat4::rotation_y(self.rotation_angle);
-        translation.multiply(&rotation)
+        self.get_orbit_matrix().multiply(&rotation)
     }
 }
\ No newline at end of file
@@ -0,0 +1,231 @@
+/// Escalas de tiempo disponibles con las teclas +/-
+const TIME_SCALES: [f32; 13] = [
+    0.1, 0.25, 0.5, 1.0, 2.0, 5.0, 10.0, 25.0, 50.0, 100.0, 250.0, 500.0, 1000.0,
+];
+
+/// Reloj de simulación con paso fijo
+///
+/// Separa la simulación del render: el tiempo real de cada frame se
+/// multiplica por la escala y se acumula, y la simulación avanza siempre en
+/// pasos de `timestep`. Con la misma secuencia de pasos el resultado es el
+/// mismo sin importar los FPS.
+#[derive(Debug, Clone)]
+pub struct SimulationClock {
+    pub timestep: f32,      // Paso fijo de simulación (segundos simulados)
+    pub time_scale: f32,    // Segundos simulados por segundo real
+    pub paused: bool,
+    pub reversed: bool,     // Simular hacia atrás
+    pub max_steps: usize,   // Pasos máximos por frame (evita la espiral de la muerte)
+    pub max_frame_time: f32, // Tiempo real máximo por frame (ventana minimizada, etc.)
+    pub(crate) accumulator: f32,
+    pub(crate) sim_time: f64, // Tiempo simulado total (negativo si se retrocede)
+    pub(crate) steps: u64,
+}
+
+impl SimulationClock {
+    pub fn new(timestep: f32) -> Self {
+        Self {
+            timestep,
+            time_scale: 1.0,
+            paused: false,
+            reversed: false,
+            max_steps: 4000,
+            max_frame_time: 0.1,
+            accumulator: 0.0,
+            sim_time: 0.0,
+            steps: 0,
+        }
+    }
+
+    /// Acumula el tiempo real de un frame y ejecuta los pasos fijos que tocan
+    ///
+    /// `step` recibe el delta de cada paso (negativo en reversa). Devuelve el
+    /// número de pasos ejecutados.
+    pub fn advance(&mut self, real_delta: f32, mut step: impl FnMut(f32)) -> usize {
+        if self.paused {
+            return 0;
+        }
+
+        self.accumulator += real_delta.clamp(0.0, self.max_frame_time) * self.time_scale;
+        let delta = self.step_delta();
+
+        let mut count = 0;
+        while self.accumulator >= self.timestep && count < self.max_steps {
+            step(delta);
+            self.accumulator -= self.timestep;
+            self.sim_time += delta as f64;
+            count += 1;
+        }
+        self.steps += count as u64;
+
+        // Si la simulación no da abasto, se descarta el atraso
+        if count == self.max_steps {
+            self.accumulator = 0.0;
+        }
+        count
+    }
+
+    /// Delta de un paso, con signo según la dirección
+    pub fn step_delta(&self) -> f32 {
+        if self.reversed { -self.timestep } else { self.timestep }
+    }
+
+    /// Tiempo simulado total en segundos
+    pub fn sim_time(&self) -> f64 {
+        self.sim_time
+    }
+
+    /// Número total de pasos ejecutados
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+
+    pub fn toggle_pause(&mut self) {
+        self.paused = !self.paused;
+    }
+
+    pub fn toggle_reverse(&mut self) {
+        self.reversed = !self.reversed;
+    }
+
+    /// Fija la escala de tiempo (entre 0.1x y 1000x)
+    pub fn set_time_scale(&mut self, scale: f32) {
+        self.time_scale = scale.clamp(TIME_SCALES[0], TIME_SCALES[TIME_SCALES.len() - 1]);
+    }
+
+    /// Siguiente escala de tiempo más rápida
+    pub fn faster(&mut self) {
+        if let Some(&scale) = TIME_SCALES.iter().find(|&&s| s > self.time_scale) {
+            self.time_scale = scale;
+        }
+    }
+
+    /// Siguiente escala de tiempo más lenta
+    pub fn slower(&mut self) {
+        if let Some(&scale) = TIME_SCALES.iter().rev().find(|&&s| s < self.time_scale) {
+            self.time_scale = scale;
+        }
+    }
+
+    /// Texto de estado, p. ej. "x10 | t = 123.4 s" o "PAUSA x1 | ..."
+    pub fn status(&self) -> String {
+        let mut status = String::new();
+        if self.paused {
+            status.push_str("PAUSA ");
+        }
+        if self.reversed {
+            status.push_str("<< ");
+        }
+        status.push_str(&format!("x{} | t = {:.1} s", self.time_scale, self.sim_time));
+        status
+    }
+}
+
+impl Default for SimulationClock {
+    fn default() -> Self {
+        Self::new(1.0 / 120.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Reloj con un paso exacto en binario: las cuentas no tienen redondeo
+    fn clock() -> SimulationClock {
+        SimulationClock::new(0.0625)
+    }
+
+    #[test]
+    fn accumulates_whole_steps() {
+        let mut clock = clock();
+        let mut deltas = Vec::new();
+
+        // 1.5 pasos: corre uno y guarda el medio paso restante
+        assert_eq!(clock.advance(0.09375, |dt| deltas.push(dt)), 1);
+        assert_eq!(clock.accumulator, 0.03125);
+        // El resto más otro 1.5 completa dos pasos justos
+        assert_eq!(clock.advance(0.09375, |dt| deltas.push(dt)), 2);
+        assert_eq!(clock.accumulator, 0.0);
+
+        assert_eq!(deltas, [0.0625; 3]);
+        assert_eq!(clock.steps(), 3);
+        assert_eq!(clock.sim_time(), 0.1875);
+
+        // Un frame largo se recorta a `max_frame_time`
+        let mut clock = self::clock();
+        clock.max_frame_time = 0.125;
+        assert_eq!(clock.advance(10.0, |_| {}), 2);
+    }
+
+    #[test]
+    fn time_scale_multiplies_real_time() {
+        let mut clock = clock();
+        clock.set_time_scale(10.0);
+        assert_eq!(clock.advance(0.0625, |_| {}), 10);
+        assert_eq!(clock.sim_time(), 0.625);
+    }
+
+    #[test]
+    fn paused_runs_no_steps() {
+        let mut clock = clock();
+        clock.toggle_pause();
+        assert_eq!(clock.advance(0.09375, |_| panic!("en pausa no se simula")), 0);
+        assert_eq!(clock.accumulator, 0.0);
+        assert_eq!(clock.sim_time(), 0.0);
+
+        clock.toggle_pause();
+        assert_eq!(clock.advance(0.0625, |_| {}), 1);
+    }
+
+    #[test]
+    fn reverse_flips_the_step_sign() {
+        let mut clock = clock();
+        clock.advance(0.0625, |_| {});
+        clock.toggle_reverse();
+
+        let mut deltas = Vec::new();
+        clock.advance(0.0625, |dt| deltas.push(dt));
+        clock.advance(0.0625, |dt| deltas.push(dt));
+        assert_eq!(deltas, [-0.0625; 2]);
+        assert_eq!(clock.sim_time(), -0.0625);
+        assert_eq!(clock.steps(), 3);
+    }
+
+    #[test]
+    fn time_scale_is_clamped() {
+        let mut clock = clock();
+        clock.set_time_scale(0.01);
+        assert_eq!(clock.time_scale, 0.1);
+        clock.slower();
+        assert_eq!(clock.time_scale, 0.1);
+
+        clock.set_time_scale(1e6);
+        assert_eq!(clock.time_scale, 1000.0);
+        clock.faster();
+        assert_eq!(clock.time_scale, 1000.0);
+
+        clock.set_time_scale(3.0);
+        clock.faster();
+        assert_eq!(clock.time_scale, 5.0);
+        clock.slower();
+        clock.slower();
+        assert_eq!(clock.time_scale, 1.0);
+    }
+
+    #[test]
+    fn backlog_is_dropped_at_max_steps() {
+        let mut clock = clock();
+        clock.max_steps = 3;
+        clock.set_time_scale(1000.0);
+
+        // 100 s simulados pedirían 1600 pasos
+        assert_eq!(clock.advance(0.1, |_| {}), 3);
+        assert_eq!(clock.accumulator, 0.0);
+        assert_eq!(clock.sim_time(), 0.1875);
+
+        // El frame siguiente no arrastra el atraso
+        clock.set_time_scale(1.0);
+        assert_eq!(clock.advance(0.0625, |_| {}), 1);
+    }
+}
@@ -1,7 +1,15 @@
 pub mod celestial_body;
 pub mod solar_system;
 pub mod orbit;
+pub mod scene_file;
+pub mod physics;
+pub mod clock;
+pub mod snapshot;
 
 pub use celestial_body::CelestialBody;
-pub use solar_system::SolarSystem;
-pub use orbit::{Orbit, OrbitType};
\ No newline at end of file
+pub use solar_system::{SolarSystem, SystemSnapshot};
+pub use orbit::{Orbit, OrbitType};
+pub use clock::SimulationClock;
+pub use physics::{BodyState, Integrator, PhysicsSettings, PhysicsWorld};
+pub use snapshot::{SNAPSHOT_VERSION, Snapshot, load_snapshot, parse_snapshot, save_snapshot, snapshot_to_string};
+pub use scene_file::{SceneError, load_scene, parse_scene, save_scene, scene_to_string};
@@ -53,77 +53,86 @@ impl Orbit {
     }
 
     /// Actualiza el ángulo de la órbita
+    ///
+    /// En órbitas elípticas `speed` es la velocidad angular media: el cuerpo
+    /// acelera en el perihelio y frena en el afelio (segunda ley de Kepler).
     pub fn update(&mut self, delta_time: f32) {
-        self.current_angle += self.speed * delta_time;
-        
-        // Mantener en rango [0, 2π]
-        if self.current_angle > std::f32::consts::TAU {
-            self.current_angle -= std::f32::consts::TAU;
-        }
+        let rate = match self.orbit_type {
+            OrbitType::Circular => self.speed,
+            OrbitType::Elliptical { eccentricity } => {
+                let e = eccentricity;
+                let factor = 1.0 + e * self.current_angle.cos();
+                self.speed * factor * factor / (1.0 - e * e).powf(1.5)
+            }
+        };
+        self.current_angle += rate * delta_time;
+
+        // Mantener en rango [0, 2π), también con velocidad negativa o dt grande
+        self.current_angle = self.current_angle.rem_euclid(std::f32::consts::TAU);
     }
 
     /// Obtiene la posición actual en la órbita
     pub fn get_position(&self) -> Vec3 {
+        self.position_at(self.current_angle)
+    }
+
+    /// Genera puntos para visualizar la órbita completa
+    pub fn generate_orbit_path(&self, segments: usize) -> Vec<Vec3> {
+        (0..segments)
+            .map(|i| self.position_at(std::f32::consts::TAU * (i as f32) / (segments as f32)))
+            .collect()
+    }
+
+    /// Dirección de movimiento actual (tangente a la órbita, según el signo de `speed`)
+    pub fn tangent(&self) -> Vec3 {
+        let epsilon = 1e-3;
+        let ahead = self.position_at(self.current_angle + epsilon);
+        let behind = self.position_at(self.current_angle - epsilon);
+        let direction = (ahead - behind).normalize();
+        if self.speed < 0.0 { -direction } else { direction }
+    }
+
+    /// Distancia al foco para un ángulo dado
+    fn radius_at(&self, angle: f32) -> f32 {
         match self.orbit_type {
-            OrbitType::Circular => {
-                let x = self.radius * self.current_angle.cos();
-                let z = self.radius * self.current_angle.sin();
-                let y = z * self.inclination.sin(); // Aplicar inclinación
-                let z_adjusted = z * self.inclination.cos();
-                Vec3::new(x, y, z_adjusted)
-            }
+            OrbitType::Circular => self.radius,
             OrbitType::Elliptical { eccentricity } => {
                 // r = a(1 - e²) / (1 + e*cos(θ))
-                let r = self.radius * (1.0 - eccentricity * eccentricity)
-                    / (1.0 + eccentricity * self.current_angle.cos());
-                let x = r * self.current_angle.cos();
-                let z = r * self.current_angle.sin();
-                let y = z * self.inclination.sin();
-                let z_adjusted = z * self.inclination.cos();
-                Vec3::new(x, y, z_adjusted)
+                self.radius * (1.0 - eccentricity * eccentricity)
+                    / (1.0 + eccentricity * angle.cos())
             }
         }
     }
 
-    /// Genera puntos para visualizar la órbita completa
-    pub fn generate_orbit_path(&self, segments: usize) -> Vec<Vec3> {
-        let mut points = Vec::with_capacity(segments);
-        
-        for i in 0..segments {
-            let angle = std::f32::consts::TAU * (i as f32) / (segments as f32);
-            
-            let (x, z) = match self.orbit_type {
-                OrbitType::Circular => {
-                    (self.radius * angle.cos(), self.radius * angle.sin())
-                }
-                OrbitType::Elliptical { eccentricity } => {
-                    let r = self.radius * (1.0 - eccentricity * eccentricity)
-                        / (1.0 + eccentricity * angle.cos());
-                    (r * angle.cos(), r * angle.sin())
-                }
-            };
-            
-            let y = z * self.inclination.sin();
-            let z_adjusted = z * self.inclination.cos();
-            
-            points.push(Vec3::new(x, y, z_adjusted));
-        }
-        
-        points
+    /// Posición en la órbita para un ángulo dado, con la inclinación aplicada
+    fn position_at(&self, angle: f32) -> Vec3 {
+        let r = self.radius_at(angle);
+        let x = r * angle.cos();
+        let z = r * angle.sin();
+        let y = z * self.inclination.sin(); // Aplicar inclinación
+        let z_adjusted = z * self.inclination.cos();
+        Vec3::new(x, y, z_adjusted)
     }
 
     /// Calcula la velocidad orbital en un punto dado (para órbitas elípticas)
     pub fn get_orbital_velocity(&self) -> f32 {
         match self.orbit_type {
             OrbitType::Circular => self.speed * self.radius,
-            OrbitType::Elliptical { eccentricity } => {
+            OrbitType::Elliptical { .. } => {
                 // Velocidad varía en órbita elíptica (más rápido en perihelio)
-                let r = self.radius * (1.0 - eccentricity * eccentricity)
-                    / (1.0 + eccentricity * self.current_angle.cos());
+                let r = self.radius_at(self.current_angle);
                 self.speed * self.radius * self.radius / r
             }
         }
     }
+
+    /// Excentricidad (0 para órbitas circulares)
+    pub fn eccentricity(&self) -> f32 {
+        match self.orbit_type {
+            OrbitType::Circular => 0.0,
+            OrbitType::Elliptical { eccentricity } => eccentricity,
+        }
+    }
 }
 
 /// Órbita nula (para el sol que está estático)
@@ -0,0 +1,250 @@
+use crate::math::Vec3;
+
+/// Método de integración numérica
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Integrator {
+    Leapfrog, // Simpléctico (kick-drift-kick): conserva bien la energía
+    Rk4,      // Runge-Kutta de 4º orden: más preciso por paso, con deriva de energía
+}
+
+impl Integrator {
+    /// Nombre usado en los archivos de escena
+    pub fn name(&self) -> &'static str {
+        match self {
+            Integrator::Leapfrog => "leapfrog",
+            Integrator::Rk4 => "rk4",
+        }
+    }
+
+    pub fn from_name(name: &str) -> Option<Self> {
+        [Integrator::Leapfrog, Integrator::Rk4]
+            .into_iter()
+            .find(|i| i.name().eq_ignore_ascii_case(name))
+    }
+}
+
+/// Parámetros de la simulación gravitacional
+#[derive(Debug, Clone, Copy)]
+pub struct PhysicsSettings {
+    pub gravity: f32,    // Constante gravitacional G
+    pub substep: f32,    // Paso fijo de integración (segundos)
+    pub softening: f32,  // Suavizado para evitar fuerzas infinitas a distancia 0
+    pub integrator: Integrator,
+    pub max_substeps: usize, // Límite de pasos por llamada (evita congelar el frame)
+}
+
+impl Default for PhysicsSettings {
+    fn default() -> Self {
+        Self {
+            gravity: 1.0,
+            substep: 1.0 / 240.0,
+            softening: 0.05,
+            integrator: Integrator::Leapfrog,
+            max_substeps: 20_000,
+        }
+    }
+}
+
+/// Estado dinámico de un cuerpo
+#[derive(Debug, Clone, Copy)]
+pub struct BodyState {
+    pub mass: f32,
+    pub position: Vec3, // Espacio mundo
+    pub velocity: Vec3,
+}
+
+/// Simulación gravitacional de N cuerpos con paso fijo
+///
+/// Todos los cuerpos se atraen entre sí (O(N²) por paso). El tiempo se
+/// acumula y se integra en pasos de `settings.substep`, así el resultado
+/// solo depende de la secuencia de `step` y no de los FPS. Un `delta_time`
+/// negativo integra hacia atrás.
+#[derive(Debug, Clone)]
+pub struct PhysicsWorld {
+    pub settings: PhysicsSettings,
+    pub states: Vec<BodyState>,
+    accelerations: Vec<Vec3>, // Aceleraciones en las posiciones actuales (leapfrog)
+    pub(crate) accumulator: f32, // Tiempo pendiente de integrar
+}
+
+impl PhysicsWorld {
+    pub fn new(settings: PhysicsSettings, states: Vec<BodyState>) -> Self {
+        let mut world = Self {
+            settings,
+            accelerations: Vec::new(),
+            states,
+            accumulator: 0.0,
+        };
+        world.accelerations = world.compute_accelerations(&world.positions());
+        world
+    }
+
+    /// Avanza la simulación `delta_time` segundos en pasos fijos
+    pub fn step(&mut self, delta_time: f32) {
+        let h = self.settings.substep;
+        self.accumulator += delta_time;
+
+        let mut steps = 0;
+        while self.accumulator.abs() >= h && steps < self.settings.max_substeps {
+            let dt = h.copysign(self.accumulator);
+            match self.settings.integrator {
+                Integrator::Leapfrog => self.leapfrog_step(dt),
+                Integrator::Rk4 => self.rk4_step(dt),
+            }
+            self.accumulator -= dt;
+            steps += 1;
+        }
+
+        // Si se alcanzó el límite, descartar el tiempo atrasado
+        if steps == self.settings.max_substeps {
+            self.accumulator = 0.0;
+        }
+    }
+
+    /// Energía total (cinética + potencial), útil para medir la deriva
+    pub fn total_energy(&self) -> f32 {
+        let g = self.settings.gravity;
+        let eps2 = self.settings.softening * self.settings.softening;
+        let mut energy = 0.0;
+        for (i, a) in self.states.iter().enumerate() {
+            energy += 0.5 * a.mass * a.velocity.dot(&a.velocity);
+            for b in &self.states[i + 1..] {
+                let d = b.position - a.position;
+                energy -= g * a.mass * b.mass / (d.dot(&d) + eps2).sqrt();
+            }
+        }
+        energy
+    }
+
+    fn positions(&self) -> Vec<Vec3> {
+        self.states.iter().map(|s| s.position).collect()
+    }
+
+    /// Aceleración gravitacional de cada cuerpo para unas posiciones dadas
+    fn compute_accelerations(&self, positions: &[Vec3]) -> Vec<Vec3> {
+        let g = self.settings.gravity;
+        let eps2 = self.settings.softening * self.settings.softening;
+        let mut accelerations = vec![Vec3::zero(); positions.len()];
+
+        for i in 0..positions.len() {
+            for j in (i + 1)..positions.len() {
+                let d = positions[j] - positions[i];
+                let dist2 = d.dot(&d) + eps2;
+                let inv_dist3 = 1.0 / (dist2 * dist2.sqrt());
+                // Par acción-reacción: cada par se calcula una sola vez
+                accelerations[i] = accelerations[i] + d * (g * self.states[j].mass * inv_dist3);
+                accelerations[j] = accelerations[j] - d * (g * self.states[i].mass * inv_dist3);
+            }
+        }
+        accelerations
+    }
+
+    /// Leapfrog kick-drift-kick (simpléctico y reversible en el tiempo)
+    fn leapfrog_step(&mut self, dt: f32) {
+        for (state, acc) in self.states.iter_mut().zip(&self.accelerations) {
+            state.velocity = state.velocity + *acc * (0.5 * dt);
+            state.position = state.position + state.velocity * dt;
+        }
+        self.accelerations = self.compute_accelerations(&self.positions());
+        for (state, acc) in self.states.iter_mut().zip(&self.accelerations) {
+            state.velocity = state.velocity + *acc * (0.5 * dt);
+        }
+    }
+
+    /// Runge-Kutta clásico de 4º orden sobre (posición, velocidad)
+    fn rk4_step(&mut self, dt: f32) {
+        let x0 = self.positions();
+        let v0: Vec<Vec3> = self.states.iter().map(|s| s.velocity).collect();
+        let offset = |base: &[Vec3], delta: &[Vec3], s: f32| -> Vec<Vec3> {
+            base.iter().zip(delta).map(|(b, d)| *b + *d * s).collect()
+        };
+
+        let a1 = self.compute_accelerations(&x0);
+        let v1 = v0.clone();
+
+        let x2 = offset(&x0, &v1, 0.5 * dt);
+        let v2 = offset(&v0, &a1, 0.5 * dt);
+        let a2 = self.compute_accelerations(&x2);
+
+        let x3 = offset(&x0, &v2, 0.5 * dt);
+        let v3 = offset(&v0, &a2, 0.5 * dt);
+        let a3 = self.compute_accelerations(&x3);
+
+        let x4 = offset(&x0, &v3, dt);
+        let v4 = offset(&v0, &a3, dt);
+        let a4 = self.compute_accelerations(&x4);
+
+        for (i, state) in self.states.iter_mut().enumerate() {
+            state.position = x0[i] + (v1[i] + v2[i] * 2.0 + v3[i] * 2.0 + v4[i]) * (dt / 6.0);
+            state.velocity = v0[i] + (a1[i] + a2[i] * 2.0 + a3[i] * 2.0 + a4[i]) * (dt / 6.0);
+        }
+        self.accelerations = self.compute_accelerations(&self.positions());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Estrella y planeta en órbita circular, con momento total nulo
+    fn circular_orbit(integrator: Integrator) -> PhysicsWorld {
+        let (star_mass, planet_mass, radius): (f32, f32, f32) = (100.0, 0.01, 10.0);
+        let speed = (star_mass / radius).sqrt();
+        let settings = PhysicsSettings { integrator, ..PhysicsSettings::default() };
+        PhysicsWorld::new(
+            settings,
+            vec![
+                BodyState {
+                    mass: star_mass,
+                    position: Vec3::zero(),
+                    velocity: Vec3::new(0.0, 0.0, -speed * planet_mass / star_mass),
+                },
+                BodyState {
+                    mass: planet_mass,
+                    position: Vec3::new(radius, 0.0, 0.0),
+                    velocity: Vec3::new(0.0, 0.0, speed),
+                },
+            ],
+        )
+    }
+
+    #[test]
+    fn leapfrog_energy_drift_stays_bounded() {
+        let mut world = circular_orbit(Integrator::Leapfrog);
+        let initial = world.total_energy();
+
+        // Un período completo (~20 s), en frames de 60 FPS
+        let mut max_drift: f32 = 0.0;
+        for _ in 0..1200 {
+            world.step(1.0 / 60.0);
+            max_drift = max_drift.max(((world.total_energy() - initial) / initial).abs());
+        }
+        assert!(max_drift < 1e-4, "deriva relativa de energía {}", max_drift);
+
+        // Sigue en la órbita circular
+        let d = world.states[1].position - world.states[0].position;
+        assert!((d.length() - 10.0).abs() < 0.01, "radio {}", d.length());
+    }
+
+    #[test]
+    fn stepping_back_returns_to_start() {
+        for integrator in [Integrator::Leapfrog, Integrator::Rk4] {
+            let mut world = circular_orbit(integrator);
+            let start = world.states.clone();
+
+            // Frames del reloj por defecto (dos subpasos cada uno), 5 s y vuelta
+            for _ in 0..600 {
+                world.step(1.0 / 120.0);
+            }
+            for _ in 0..600 {
+                world.step(-1.0 / 120.0);
+            }
+
+            for (now, before) in world.states.iter().zip(&start) {
+                let dp = (now.position - before.position).length();
+                let dv = (now.velocity - before.velocity).length();
+                assert!(dp < 1e-3 && dv < 1e-3, "{}: error {} / {}", integrator.name(), dp, dv);
+            }
+        }
+    }
+}
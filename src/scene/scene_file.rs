@@ -0,0 +1,469 @@
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::config::{ConfigError, Document, DocumentWriter, Table};
+use crate::shaders::{PointLight, ShaderType};
+use super::celestial_body::CelestialBody;
+use super::orbit::Orbit;
+use super::physics::{Integrator, PhysicsSettings};
+use super::solar_system::SolarSystem;
+
+const ROOT_KEYS: &[&str] = &["name"];
+const CAMERA_KEYS: &[&str] = &["target", "distance"];
+const LIGHT_KEYS: &[&str] = &["color", "intensity", "falloff"];
+const PHYSICS_KEYS: &[&str] = &["enabled", "gravity", "substep", "softening", "integrator"];
+const BODY_KEYS: &[&str] = &[
+    "name",
+    "parent",
+    "radius",
+    "mass",
+    "color",
+    "shader",
+    "emissive",
+    "rotation_speed",
+    "orbit_radius",
+    "orbit_speed",
+    "eccentricity",
+    "inclination",
+    "orbit_angle",
+    "rotation_angle",
+    "show_orbit",
+    "show_label",
+    "detail",
+];
+
+/// Error al cargar o guardar un archivo de escena
+#[derive(Debug)]
+pub enum SceneError {
+    Io(PathBuf, io::Error),
+    Invalid(ConfigError),
+}
+
+impl fmt::Display for SceneError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SceneError::Io(path, err) => write!(f, "{}: {}", path.display(), err),
+            SceneError::Invalid(err) => write!(f, "escena inválida, {}", err),
+        }
+    }
+}
+
+impl std::error::Error for SceneError {}
+
+impl From<ConfigError> for SceneError {
+    fn from(err: ConfigError) -> Self {
+        SceneError::Invalid(err)
+    }
+}
+
+/// Carga un sistema solar desde un archivo de escena
+pub fn load_scene(path: &Path) -> Result<SolarSystem, SceneError> {
+    let text = fs::read_to_string(path).map_err(|err| SceneError::Io(path.to_path_buf(), err))?;
+    parse_scene(&text)
+}
+
+/// Guarda el sistema solar (en su estado actual) como archivo de escena
+pub fn save_scene(system: &SolarSystem, path: &Path) -> Result<(), SceneError> {
+    let io_error = |err| SceneError::Io(path.to_path_buf(), err);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(io_error)?;
+    }
+    let text = scene_to_string(system)?;
+    fs::write(path, text).map_err(io_error)
+}
+
+/// Interpreta y valida el texto de un archivo de escena
+///
+/// Formato (subconjunto de TOML):
+///
+/// ```toml
+/// name = "Space Travel"
+///
+/// [camera]
+/// target = "Solar heart"   # nombre del cuerpo a seguir
+/// distance = 35.0
+///
+/// [light]                  # opcional
+/// color = [1.0, 0.95, 0.85]
+/// intensity = 1.1
+/// falloff = 0.0008
+///
+/// [physics]                # opcional, gravedad N-cuerpos
+/// enabled = false          # true = la gravedad reemplaza las órbitas fijas
+/// gravity = 1.0            # constante G
+/// substep = 0.004166       # paso fijo de integración (segundos)
+/// softening = 0.05
+/// integrator = "leapfrog"  # leapfrog o rk4
+///
+/// [[body]]
+/// name = "Solar heart"
+/// parent = "..."           # opcional, cuerpo definido antes al que orbita (luna)
+/// radius = 2.5
+/// mass = 200.0             # opcional (radius³); la estrella debe ir antes que sus planetas
+/// color = [1.0, 0.9, 0.3]
+/// shader = "solar_heart"   # solar_heart, rocky, gas_giant, lava, ice, alien
+/// emissive = true          # opcional (false)
+/// rotation_speed = 0.1     # opcional (0)
+/// orbit_radius = 0.0       # opcional (0 = sin órbita)
+/// orbit_speed = 0.0        # opcional (0), velocidad angular media (radianes/segundo)
+/// eccentricity = 0.0       # opcional (0 = circular), entre 0 y 1
+/// inclination = 0.0        # opcional, respecto al plano eclíptico (radianes)
+/// orbit_angle = 0.0        # opcional, ángulo actual en la órbita (radianes)
+/// rotation_angle = 0.0     # opcional, ángulo de rotación propia (radianes)
+/// show_orbit = true        # opcional, dibujar la línea de la órbita
+/// show_label = true        # opcional (igual que show_orbit), dibujar el nombre en pantalla
+/// detail = 24              # opcional, segmentos de la esfera
+/// ```
+pub fn parse_scene(text: &str) -> Result<SolarSystem, SceneError> {
+    scene_from_document(&Document::parse(text)?, &[])
+}
+
+/// Construye el sistema desde un documento ya interpretado
+///
+/// `extra_tables` son tablas adicionales permitidas (p. ej. las de un snapshot).
+pub(crate) fn scene_from_document(document: &Document, extra_tables: &[&str]) -> Result<SolarSystem, SceneError> {
+    let known = ["camera", "light", "physics", "body"];
+    let allowed: Vec<&str> = known.iter().chain(extra_tables).copied().collect();
+    document.check_tables(&allowed)?;
+    document.root.check_keys(ROOT_KEYS)?;
+
+    let name = document.root.string("name")?.unwrap_or("Sistema sin nombre");
+
+    let mut bodies: Vec<CelestialBody> = Vec::new();
+    for table in document.tables_named("body") {
+        let mut body = parse_body(table)?;
+        if bodies.iter().any(|b| b.name == body.name) {
+            return Err(ConfigError::new(
+                table.line_of("name"),
+                format!("cuerpo '{}' duplicado", body.name),
+            )
+            .into());
+        }
+        // El padre debe estar definido antes, así la jerarquía no tiene ciclos
+        if let Some(parent) = table.string("parent")? {
+            let index = bodies.iter().position(|b| b.name == parent).ok_or_else(|| {
+                ConfigError::new(
+                    table.line_of("parent"),
+                    format!("el padre '{}' debe ser un cuerpo definido antes que '{}'", parent, body.name),
+                )
+            })?;
+            body.parent = Some(index);
+        }
+        bodies.push(body);
+    }
+    if bodies.is_empty() {
+        return Err(ConfigError::new(0, "la escena no tiene ningún [[body]]").into());
+    }
+
+    let mut system = SolarSystem::from_bodies(name, bodies);
+
+    if let Some(table) = document.table("light") {
+        system.light = parse_light(table)?;
+        system.sync_light();
+    }
+
+    if let Some(table) = document.table("physics") {
+        let enabled = parse_physics(table, &mut system.physics_settings)?;
+        system.set_physics(enabled);
+    }
+
+    if let Some(table) = document.table("camera") {
+        table.check_keys(CAMERA_KEYS)?;
+        if let Some(target) = table.string("target")? {
+            system.camera_target = system
+                .bodies
+                .iter()
+                .position(|b| b.name == target)
+                .ok_or_else(|| {
+                    ConfigError::new(
+                        table.line_of("target"),
+                        format!("el objetivo de la cámara '{}' no es un cuerpo de la escena", target),
+                    )
+                })?;
+        }
+        if let Some(distance) = table.f32("distance")? {
+            if distance <= 0.0 {
+                return Err(positive_error(table, "distance").into());
+            }
+            system.camera_distance = distance;
+        }
+    }
+
+    Ok(system)
+}
+
+/// Convierte el sistema solar en texto de escena (inverso de `parse_scene`)
+///
+/// Falla si algún valor no es finito (p. ej. tras una explosión de la física),
+/// porque el archivo no se podría volver a cargar.
+pub fn scene_to_string(system: &SolarSystem) -> Result<String, SceneError> {
+    let mut writer = DocumentWriter::new();
+    writer
+        .comment("Escena de Space Travel")
+        .string("name", &system.name);
+
+    writer.table("camera");
+    if let Some(target) = system.get_body(system.camera_target) {
+        writer.string("target", &target.name);
+    }
+    writer.number("distance", system.camera_distance);
+
+    let light = &system.light;
+    writer
+        .table("light")
+        .numbers("color", &[light.color.0, light.color.1, light.color.2])
+        .number("intensity", light.intensity)
+        .number("falloff", light.falloff);
+
+    let physics = &system.physics_settings;
+    writer
+        .table("physics")
+        .bool("enabled", system.physics_enabled())
+        .number("gravity", physics.gravity)
+        .number("substep", physics.substep)
+        .number("softening", physics.softening)
+        .string("integrator", physics.integrator.name());
+
+    for body in &system.bodies {
+        writer.array_table("body").string("name", &body.name);
+        if let Some(parent) = body.parent.and_then(|p| system.get_body(p)) {
+            writer.string("parent", &parent.name);
+        }
+        writer
+            .number("radius", body.radius)
+            .number("mass", body.mass)
+            .numbers("color", &[body.color.0, body.color.1, body.color.2])
+            .string("shader", body.shader_type.name())
+            .bool("emissive", body.is_emissive)
+            .number("rotation_speed", body.rotation_speed)
+            .number("orbit_radius", body.orbit.radius)
+            .number("orbit_speed", body.orbit.speed)
+            .number("eccentricity", body.orbit.eccentricity())
+            .number("inclination", body.orbit.inclination)
+            .number("orbit_angle", body.orbit.current_angle)
+            .number("rotation_angle", body.rotation_angle)
+            .bool("show_orbit", body.show_orbit)
+            .bool("show_label", body.show_label)
+            .integer("detail", body.detail);
+    }
+
+    Ok(writer.finish()?)
+}
+
+/// Lee y valida un `[[body]]`
+fn parse_body(table: &Table) -> Result<CelestialBody, ConfigError> {
+    table.check_keys(BODY_KEYS)?;
+
+    let name = table.string("name")?.ok_or_else(|| table.missing("name"))?;
+    if name.trim().is_empty() {
+        return Err(ConfigError::new(table.line_of("name"), "el nombre no puede estar vacío"));
+    }
+
+    let radius = table.f32("radius")?.ok_or_else(|| table.missing("radius"))?;
+    if radius <= 0.0 {
+        return Err(positive_error(table, "radius"));
+    }
+
+    let color = table.f32_array::<3>("color")?.ok_or_else(|| table.missing("color"))?;
+    if color.iter().any(|c| !(0.0..=1.0).contains(c)) {
+        return Err(ConfigError::new(
+            table.line_of("color"),
+            "los componentes de 'color' deben estar entre 0 y 1",
+        ));
+    }
+
+    let shader_name = table.string("shader")?.ok_or_else(|| table.missing("shader"))?;
+    let shader_type = ShaderType::from_name(shader_name).ok_or_else(|| {
+        let known: Vec<&str> = ShaderType::ALL.iter().map(|s| s.name()).collect();
+        ConfigError::new(
+            table.line_of("shader"),
+            format!("shader desconocido '{}' (opciones: {})", shader_name, known.join(", ")),
+        )
+    })?;
+
+    let orbit_radius = table.f32("orbit_radius")?.unwrap_or(0.0);
+    if orbit_radius < 0.0 {
+        return Err(ConfigError::new(
+            table.line_of("orbit_radius"),
+            "'orbit_radius' no puede ser negativo",
+        ));
+    }
+
+    let orbit_speed = table.f32("orbit_speed")?.unwrap_or(0.0);
+    let eccentricity = table.f32("eccentricity")?.unwrap_or(0.0);
+    if !(0.0..1.0).contains(&eccentricity) {
+        return Err(ConfigError::new(
+            table.line_of("eccentricity"),
+            "'eccentricity' debe estar en el rango [0, 1)",
+        ));
+    }
+    let orbit = if eccentricity > 0.0 {
+        Orbit::elliptical(orbit_radius, eccentricity, orbit_speed)
+    } else {
+        Orbit::circular(orbit_radius, orbit_speed)
+    };
+    let orbit = orbit
+        .with_inclination(table.f32("inclination")?.unwrap_or(0.0))
+        .with_initial_angle(table.f32("orbit_angle")?.unwrap_or(0.0).rem_euclid(std::f32::consts::TAU));
+
+    let detail = table
+        .usize("detail")?
+        .unwrap_or_else(|| CelestialBody::default_detail(radius));
+    if !(3..=128).contains(&detail) {
+        return Err(ConfigError::new(
+            table.line_of("detail"),
+            "'detail' debe estar entre 3 y 128",
+        ));
+    }
+
+    let mass = table.f32("mass")?;
+    if mass.is_some_and(|m| m <= 0.0) {
+        return Err(positive_error(table, "mass"));
+    }
+
+    let mut body = CelestialBody::new(
+        name,
+        radius,
+        (color[0], color[1], color[2]),
+        orbit_radius,
+        orbit_speed,
+        table.f32("rotation_speed")?.unwrap_or(0.0),
+        table.bool("emissive")?.unwrap_or(false),
+        shader_type,
+    )
+    .with_orbit(orbit);
+    if let Some(mass) = mass {
+        body.mass = mass;
+    }
+    body.show_orbit = table.bool("show_orbit")?.unwrap_or(true);
+    // Los cuerpos sin órbita visible (p. ej. asteroides) tampoco llevan nombre
+    body.show_label = table.bool("show_label")?.unwrap_or(body.show_orbit);
+    body.rotation_angle = table.f32("rotation_angle")?.unwrap_or(0.0).rem_euclid(std::f32::consts::TAU);
+    Ok(if detail == body.detail { body } else { body.with_detail(detail) })
+}
+
+/// Lee y valida la tabla `[light]`
+fn parse_light(table: &Table) -> Result<PointLight, ConfigError> {
+    table.check_keys(LIGHT_KEYS)?;
+    let mut light = PointLight::default();
+
+    if let Some(color) = table.f32_array::<3>("color")? {
+        if color.iter().any(|c| *c < 0.0) {
+            return Err(ConfigError::new(
+                table.line_of("color"),
+                "los componentes de 'color' no pueden ser negativos",
+            ));
+        }
+        light.color = (color[0], color[1], color[2]);
+    }
+    if let Some(intensity) = table.f32("intensity")? {
+        if intensity < 0.0 {
+            return Err(ConfigError::new(table.line_of("intensity"), "'intensity' no puede ser negativa"));
+        }
+        light.intensity = intensity;
+    }
+    if let Some(falloff) = table.f32("falloff")? {
+        if falloff < 0.0 {
+            return Err(ConfigError::new(table.line_of("falloff"), "'falloff' no puede ser negativo"));
+        }
+        light.falloff = falloff;
+    }
+    Ok(light)
+}
+
+/// Lee y valida la tabla `[physics]`; devuelve si la física está activa
+fn parse_physics(table: &Table, settings: &mut PhysicsSettings) -> Result<bool, ConfigError> {
+    table.check_keys(PHYSICS_KEYS)?;
+
+    if let Some(gravity) = table.f32("gravity")? {
+        if gravity < 0.0 {
+            return Err(ConfigError::new(table.line_of("gravity"), "'gravity' no puede ser negativa"));
+        }
+        settings.gravity = gravity;
+    }
+    if let Some(substep) = table.f32("substep")? {
+        if substep <= 0.0 {
+            return Err(positive_error(table, "substep"));
+        }
+        settings.substep = substep;
+    }
+    if let Some(softening) = table.f32("softening")? {
+        if softening < 0.0 {
+            return Err(ConfigError::new(table.line_of("softening"), "'softening' no puede ser negativo"));
+        }
+        settings.softening = softening;
+    }
+    if let Some(name) = table.string("integrator")? {
+        settings.integrator = Integrator::from_name(name).ok_or_else(|| {
+            ConfigError::new(
+                table.line_of("integrator"),
+                format!("integrador desconocido '{}' (opciones: leapfrog, rk4)", name),
+            )
+        })?;
+    }
+    Ok(table.bool("enabled")?.unwrap_or(false))
+}
+
+fn positive_error(table: &Table, key: &str) -> ConfigError {
+    ConfigError::new(table.line_of(key), format!("'{}' debe ser mayor que cero", key))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scene_round_trips_through_text() {
+        let mut system = SolarSystem::new();
+        // Estado no trivial: ángulos avanzados y un cuerpo sin nombre en pantalla
+        system.update(3.7);
+        system.bodies[1].show_label = false;
+        system.set_physics(true);
+
+        let text = scene_to_string(&system).unwrap();
+        let restored = parse_scene(&text).unwrap();
+        assert_eq!(scene_to_string(&restored).unwrap(), text);
+
+        assert_eq!(restored.name, system.name);
+        assert_eq!(restored.camera_target, system.camera_target);
+        assert!(restored.physics_enabled());
+        for (a, b) in system.bodies.iter().zip(&restored.bodies) {
+            assert_eq!(a.name, b.name);
+            assert_eq!(a.parent, b.parent);
+            assert_eq!(a.shader_type, b.shader_type);
+            assert_eq!(a.show_orbit, b.show_orbit);
+            assert_eq!(a.show_label, b.show_label);
+            assert_eq!(a.orbit.current_angle.to_bits(), b.orbit.current_angle.to_bits());
+            assert_eq!(a.orbit.eccentricity().to_bits(), b.orbit.eccentricity().to_bits());
+            assert_eq!(a.rotation_angle.to_bits(), b.rotation_angle.to_bits());
+        }
+    }
+
+    #[test]
+    fn show_label_follows_show_orbit_by_default() {
+        let text = "name = \"x\"\n\n[[body]]\nname = \"a\"\nradius = 1\ncolor = [1, 1, 1]\nshader = \"rocky\"\nshow_orbit = false\n";
+        let system = parse_scene(text).unwrap();
+        assert!(!system.bodies[0].show_label);
+    }
+
+    #[test]
+    fn non_finite_state_fails_at_save_time() {
+        let mut system = SolarSystem::new();
+        system.bodies[1].orbit.current_angle = f32::NAN;
+        match scene_to_string(&system) {
+            Err(SceneError::Invalid(err)) => assert!(err.message.contains("orbit_angle"), "{}", err),
+            other => panic!("se esperaba un error de valor no finito: {:?}", other.map(|_| ())),
+        }
+    }
+
+    #[test]
+    fn scene_errors_report_their_line() {
+        let text = "name = \"x\"\n\n[[body]]\nname = \"a\"\nradius = 1\ncolor = [1, 1, 1]\nshader = \"plasma\"\n";
+        match parse_scene(text) {
+            Err(SceneError::Invalid(err)) => assert_eq!(err.line, 7),
+            other => panic!("se esperaba un error en la línea 7: {:?}", other.map(|_| ())),
+        }
+    }
+}
@@ -0,0 +1,377 @@
+use std::fs;
+use std::path::Path;
+
+use crate::camera::{CameraMode, CameraSnapshot};
+use crate::config::{ConfigError, Document, DocumentWriter, Table};
+use crate::math::{Quat, Vec3};
+use super::clock::SimulationClock;
+use super::physics::{BodyState, PhysicsWorld};
+use super::scene_file::{SceneError, scene_from_document, scene_to_string};
+use super::solar_system::SolarSystem;
+
+/// Versión actual del formato de snapshot
+pub const SNAPSHOT_VERSION: usize = 1;
+
+const SNAPSHOT_TABLES: &[&str] = &["snapshot", "clock", "view", "state"];
+const SNAPSHOT_KEYS: &[&str] = &["version", "shader_time", "camera_target", "physics_accumulator"];
+const CLOCK_KEYS: &[&str] = &[
+    "timestep",
+    "time_scale",
+    "paused",
+    "reversed",
+    "sim_time",
+    "accumulator",
+    "steps",
+];
+const VIEW_KEYS: &[&str] = &[
+    "position",
+    "target",
+    "yaw",
+    "pitch",
+    "distance",
+    "fov",
+    "mode",
+    "orientation",
+    "velocity",
+];
+const STATE_KEYS: &[&str] = &["body", "orbit_angle", "rotation_angle", "position", "velocity"];
+
+/// Estado de la aplicación que acompaña al sistema en un snapshot
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub camera: CameraSnapshot,
+    pub camera_target: usize, // Cuerpo que sigue la cámara
+    pub clock: SimulationClock,
+    pub shader_time: f32,     // Tiempo de las animaciones de shaders
+}
+
+/// Guarda un snapshot completo: la escena (para poder compartirlo) más el
+/// estado exacto de la simulación, la cámara y el reloj
+pub fn save_snapshot(path: &Path, system: &SolarSystem, snapshot: &Snapshot) -> Result<(), SceneError> {
+    let io_error = |err| SceneError::Io(path.to_path_buf(), err);
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(io_error)?;
+    }
+    let text = snapshot_to_string(system, snapshot)?;
+    fs::write(path, text).map_err(io_error)
+}
+
+/// Carga un snapshot; devuelve el sistema ya restaurado y el resto del estado
+pub fn load_snapshot(path: &Path) -> Result<(SolarSystem, Snapshot), SceneError> {
+    let text = fs::read_to_string(path).map_err(|err| SceneError::Io(path.to_path_buf(), err))?;
+    parse_snapshot(&text)
+}
+
+/// Convierte un snapshot en texto
+///
+/// Los números se escriben con todos sus dígitos, así restaurar es exacto.
+/// Falla si algún valor no es finito: ese snapshot no se podría cargar.
+pub fn snapshot_to_string(system: &SolarSystem, snapshot: &Snapshot) -> Result<String, SceneError> {
+    let state = system.snapshot();
+    // Primero la escena, que define los cuerpos
+    let mut text = scene_to_string(system)?;
+    text.push('\n');
+
+    let mut writer = DocumentWriter::new();
+    writer
+        .comment("Estado de la simulación (snapshot)")
+        .table("snapshot")
+        .integer("version", SNAPSHOT_VERSION)
+        .number("shader_time", snapshot.shader_time);
+    if let Some(target) = system.get_body(snapshot.camera_target) {
+        writer.string("camera_target", &target.name);
+    }
+    if let Some(physics) = &state.physics {
+        writer.number("physics_accumulator", physics.accumulator);
+    }
+
+    let clock = &snapshot.clock;
+    writer
+        .table("clock")
+        .number("timestep", clock.timestep)
+        .number("time_scale", clock.time_scale)
+        .bool("paused", clock.paused)
+        .bool("reversed", clock.reversed)
+        .number_f64("sim_time", clock.sim_time)
+        .number("accumulator", clock.accumulator)
+        .integer("steps", clock.steps as usize);
+
+    let camera = &snapshot.camera;
+    writer
+        .table("view")
+        .numbers("position", &vec3_array(camera.position))
+        .numbers("target", &vec3_array(camera.target))
+        .number("yaw", camera.yaw)
+        .number("pitch", camera.pitch)
+        .number("distance", camera.distance_from_target)
+        .number("fov", camera.fov)
+        .string("mode", camera.mode.name())
+        .numbers(
+            "orientation",
+            &[camera.orientation.w, camera.orientation.x, camera.orientation.y, camera.orientation.z],
+        )
+        .numbers("velocity", &vec3_array(camera.velocity));
+
+    for (i, body) in system.bodies.iter().enumerate() {
+        writer
+            .array_table("state")
+            .string("body", &body.name)
+            .number("orbit_angle", state.orbit_angles[i])
+            .number("rotation_angle", state.rotation_angles[i]);
+        if let Some(physics) = &state.physics {
+            writer
+                .numbers("position", &vec3_array(physics.states[i].position))
+                .numbers("velocity", &vec3_array(physics.states[i].velocity));
+        }
+    }
+
+    text.push_str(&writer.finish()?);
+    Ok(text)
+}
+
+/// Interpreta y valida el texto de un snapshot
+pub fn parse_snapshot(text: &str) -> Result<(SolarSystem, Snapshot), SceneError> {
+    let document = Document::parse(text)?;
+
+    let header = document
+        .table("snapshot")
+        .ok_or_else(|| ConfigError::new(0, "no es un snapshot (falta la tabla [snapshot])"))?;
+    header.check_keys(SNAPSHOT_KEYS)?;
+    let version = header.usize("version")?.ok_or_else(|| header.missing("version"))?;
+    if version == 0 || version > SNAPSHOT_VERSION {
+        return Err(ConfigError::new(
+            header.line_of("version"),
+            format!("versión de snapshot {} no soportada (máximo {})", version, SNAPSHOT_VERSION),
+        )
+        .into());
+    }
+
+    let mut system = scene_from_document(&document, SNAPSHOT_TABLES)?;
+
+    let camera_target = match header.string("camera_target")? {
+        Some(name) => system.bodies.iter().position(|b| b.name == name).ok_or_else(|| {
+            ConfigError::new(
+                header.line_of("camera_target"),
+                format!("el objetivo '{}' no es un cuerpo de la escena", name),
+            )
+        })?,
+        None => system.camera_target,
+    };
+
+    // Estado de cada cuerpo, en el mismo orden que [[body]]
+    let states: Vec<&Table> = document.tables_named("state").collect();
+    if states.len() != system.body_count() {
+        return Err(ConfigError::new(
+            states.first().map_or(header.line, |t| t.line),
+            format!("hay {} [[state]] para {} cuerpos", states.len(), system.body_count()),
+        )
+        .into());
+    }
+    let mut state = system.snapshot();
+    let mut physics_states = Vec::with_capacity(states.len());
+    for (i, table) in states.iter().enumerate() {
+        table.check_keys(STATE_KEYS)?;
+        let name = table.string("body")?.ok_or_else(|| table.missing("body"))?;
+        if name != system.bodies[i].name {
+            return Err(ConfigError::new(
+                table.line_of("body"),
+                format!("se esperaba el estado de '{}', no de '{}'", system.bodies[i].name, name),
+            )
+            .into());
+        }
+        state.orbit_angles[i] = table.f32("orbit_angle")?.ok_or_else(|| table.missing("orbit_angle"))?;
+        state.rotation_angles[i] = table
+            .f32("rotation_angle")?
+            .ok_or_else(|| table.missing("rotation_angle"))?;
+
+        if system.physics_enabled() {
+            let position = table.f32_array::<3>("position")?.ok_or_else(|| table.missing("position"))?;
+            let velocity = table.f32_array::<3>("velocity")?.ok_or_else(|| table.missing("velocity"))?;
+            physics_states.push(BodyState {
+                mass: system.bodies[i].mass,
+                position: Vec3::new(position[0], position[1], position[2]),
+                velocity: Vec3::new(velocity[0], velocity[1], velocity[2]),
+            });
+        }
+    }
+    if system.physics_enabled() {
+        let mut physics = PhysicsWorld::new(system.physics_settings, physics_states);
+        physics.accumulator = header.f32("physics_accumulator")?.unwrap_or(0.0);
+        state.physics = Some(physics);
+    }
+    system
+        .restore(&state)
+        .map_err(|message| ConfigError::new(header.line, message))?;
+
+    let snapshot = Snapshot {
+        camera: parse_view(&document)?,
+        camera_target,
+        clock: parse_clock(&document)?,
+        shader_time: header.f32("shader_time")?.unwrap_or(0.0),
+    };
+    Ok((system, snapshot))
+}
+
+/// Lee la tabla `[clock]`
+fn parse_clock(document: &Document) -> Result<SimulationClock, ConfigError> {
+    let mut clock = SimulationClock::default();
+    let table = match document.table("clock") {
+        Some(table) => table,
+        None => return Ok(clock),
+    };
+    table.check_keys(CLOCK_KEYS)?;
+
+    if let Some(timestep) = table.f32("timestep")? {
+        if timestep <= 0.0 {
+            return Err(ConfigError::new(table.line_of("timestep"), "'timestep' debe ser mayor que cero"));
+        }
+        clock.timestep = timestep;
+    }
+    if let Some(scale) = table.f32("time_scale")? {
+        clock.set_time_scale(scale);
+    }
+    clock.paused = table.bool("paused")?.unwrap_or(false);
+    clock.reversed = table.bool("reversed")?.unwrap_or(false);
+    clock.sim_time = table.f64("sim_time")?.unwrap_or(0.0);
+    clock.accumulator = table.f32("accumulator")?.unwrap_or(0.0);
+    clock.steps = table.usize("steps")?.unwrap_or(0) as u64;
+    Ok(clock)
+}
+
+/// Lee la tabla `[view]` (estado de la cámara)
+fn parse_view(document: &Document) -> Result<CameraSnapshot, ConfigError> {
+    let table = document
+        .table("view")
+        .ok_or_else(|| ConfigError::new(0, "falta la tabla [view] del snapshot"))?;
+    table.check_keys(VIEW_KEYS)?;
+
+    let position = table.f32_array::<3>("position")?.ok_or_else(|| table.missing("position"))?;
+    let target = table.f32_array::<3>("target")?.ok_or_else(|| table.missing("target"))?;
+    let mode = table.string("mode")?.ok_or_else(|| table.missing("mode"))?;
+    let mode = CameraMode::from_name(mode).ok_or_else(|| {
+        ConfigError::new(
+            table.line_of("mode"),
+            format!("modo de cámara desconocido '{}' (se espera orbit o free)", mode),
+        )
+    })?;
+    let orientation = table.f32_array::<4>("orientation")?.ok_or_else(|| table.missing("orientation"))?;
+    let velocity = table.f32_array::<3>("velocity")?.ok_or_else(|| table.missing("velocity"))?;
+    Ok(CameraSnapshot {
+        position: Vec3::new(position[0], position[1], position[2]),
+        target: Vec3::new(target[0], target[1], target[2]),
+        yaw: table.f32("yaw")?.ok_or_else(|| table.missing("yaw"))?,
+        pitch: table.f32("pitch")?.ok_or_else(|| table.missing("pitch"))?,
+        distance_from_target: table.f32("distance")?.ok_or_else(|| table.missing("distance"))?,
+        fov: table.f32("fov")?.ok_or_else(|| table.missing("fov"))?,
+        mode,
+        orientation: Quat::new(orientation[0], orientation[1], orientation[2], orientation[3]),
+        velocity: Vec3::new(velocity[0], velocity[1], velocity[2]),
+    })
+}
+
+fn vec3_array(v: Vec3) -> [f32; 3] {
+    [v.x, v.y, v.z]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::camera::{Camera, FlightInput};
+
+    fn bits(v: Vec3) -> [u32; 3] {
+        [v.x.to_bits(), v.y.to_bits(), v.z.to_bits()]
+    }
+
+    #[test]
+    fn snapshot_round_trips_exactly() {
+        let mut system = SolarSystem::new();
+        system.set_physics(true);
+        let mut clock = SimulationClock::default();
+        clock.set_time_scale(5.0);
+        for _ in 0..10 {
+            clock.advance(0.0173, |dt| system.update(dt));
+        }
+        // En reversa y con medio paso pendiente
+        clock.toggle_reverse();
+        clock.advance(0.0131, |dt| system.update(dt));
+        system.update(0.0007);
+
+        // Cámara en vuelo libre, girada y con velocidad
+        let mut camera = Camera::new(4.0 / 3.0);
+        camera.look_at_target(system.get_body_position(1));
+        camera.toggle_free_flight();
+        let input = FlightInput { thrust: Vec3::new(0.3, 0.1, 1.0), yaw: 0.7, roll: -0.2, ..FlightInput::default() };
+        for _ in 0..5 {
+            camera.fly(&input, 0.05);
+        }
+
+        let snapshot = Snapshot { camera: camera.snapshot(), camera_target: 2, clock, shader_time: 12.345 };
+        let text = snapshot_to_string(&system, &snapshot).unwrap();
+        let (restored_system, restored) = parse_snapshot(&text).unwrap();
+
+        // Estado de la simulación
+        let (before, after) = (system.snapshot(), restored_system.snapshot());
+        let angle_bits = |angles: &[f32]| angles.iter().map(|a| a.to_bits()).collect::<Vec<_>>();
+        assert_eq!(angle_bits(&after.orbit_angles), angle_bits(&before.orbit_angles));
+        assert_eq!(angle_bits(&after.rotation_angles), angle_bits(&before.rotation_angles));
+        let (physics, restored_physics) = (before.physics.unwrap(), after.physics.unwrap());
+        assert_eq!(restored_physics.accumulator.to_bits(), physics.accumulator.to_bits());
+        assert_ne!(physics.accumulator, 0.0);
+        for (a, b) in restored_physics.states.iter().zip(&physics.states) {
+            assert_eq!(a.mass.to_bits(), b.mass.to_bits());
+            assert_eq!(bits(a.position), bits(b.position));
+            assert_eq!(bits(a.velocity), bits(b.velocity));
+        }
+
+        // Reloj
+        let (c, r) = (&snapshot.clock, &restored.clock);
+        assert_eq!(r.timestep.to_bits(), c.timestep.to_bits());
+        assert_eq!(r.time_scale.to_bits(), c.time_scale.to_bits());
+        assert_eq!((r.paused, r.reversed), (c.paused, c.reversed));
+        assert!(r.reversed);
+        assert_eq!(r.sim_time.to_bits(), c.sim_time.to_bits());
+        assert_eq!(r.accumulator.to_bits(), c.accumulator.to_bits());
+        assert_eq!(r.steps, c.steps);
+
+        // Cámara y resto del estado
+        let (c, r) = (&snapshot.camera, &restored.camera);
+        assert_eq!(r.mode, CameraMode::FreeFlight);
+        assert_eq!(bits(r.position), bits(c.position));
+        assert_eq!(bits(r.target), bits(c.target));
+        assert_eq!(bits(r.velocity), bits(c.velocity));
+        assert_ne!(bits(c.velocity), [0; 3]);
+        let quat = |q: &Quat| [q.w.to_bits(), q.x.to_bits(), q.y.to_bits(), q.z.to_bits()];
+        assert_eq!(quat(&r.orientation), quat(&c.orientation));
+        for (a, b) in [(r.yaw, c.yaw), (r.pitch, c.pitch), (r.distance_from_target, c.distance_from_target), (r.fov, c.fov)] {
+            assert_eq!(a.to_bits(), b.to_bits());
+        }
+        assert_eq!(restored.camera_target, 2);
+        assert_eq!(restored.shader_time.to_bits(), snapshot.shader_time.to_bits());
+    }
+
+    #[test]
+    fn view_keys_are_required() {
+        let system = SolarSystem::new();
+        let snapshot = Snapshot {
+            camera: Camera::new(1.0).snapshot(),
+            camera_target: 0,
+            clock: SimulationClock::default(),
+            shader_time: 0.0,
+        };
+        let text = snapshot_to_string(&system, &snapshot).unwrap();
+        for key in ["mode", "orientation", "velocity", "pitch"] {
+            let without: String = text
+                .lines()
+                .filter(|line| !line.starts_with(&format!("{} = ", key)))
+                .map(|line| format!("{}\n", line))
+                .collect();
+            match parse_snapshot(&without) {
+                Err(err) => assert!(err.to_string().contains(key), "{}", err),
+                Ok(_) => panic!("se aceptó un snapshot sin '{}'", key),
+            }
+        }
+
+        let negative = text.replace("steps = 0", "steps = -3");
+        assert!(negative != text && parse_snapshot(&negative).is_err());
+    }
+}
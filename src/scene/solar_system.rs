@@ -1,108 +1,205 @@
-use crate::math::Vec3;
-use crate::renderer::generate_orbit_points;
-use crate::shaders::ShaderType;
+use crate::math::{Vec3, Mat4};
+use crate::shaders::PointLight;
+use crate::threading::PlanetThreadPool;
 use super::celestial_body::CelestialBody;
+use super::physics::{BodyState, PhysicsSettings, PhysicsWorld};
+use super::scene_file::parse_scene;
+
+/// Escena por defecto, embebida en el binario
+const DEFAULT_SCENE: &str = include_str!("../../assets/systems/default.toml");
+
+/// Estado dinámico del sistema (lo que cambia al simular)
+#[derive(Debug, Clone)]
+pub struct SystemSnapshot {
+    pub orbit_angles: Vec<f32>,
+    pub rotation_angles: Vec<f32>,
+    pub physics: Option<PhysicsWorld>, // Estado de la gravedad, si está activa
+}
 
 /// Sistema solar completo
 pub struct SolarSystem {
+    pub name: String,
     pub bodies: Vec<CelestialBody>,
-    pub orbit_points: Vec<Vec<Vec3>>, // Puntos de órbita para cada cuerpo
+    pub orbit_points: Vec<Vec<Vec3>>, // Puntos de órbita para cada cuerpo (relativos al padre)
+    pub light: PointLight,            // Luz de la estrella (sigue al cuerpo emisivo)
+    pub camera_target: usize,         // Cuerpo que sigue la cámara al iniciar
+    pub camera_distance: f32,         // Distancia inicial de la cámara
+    pub physics_settings: PhysicsSettings,
+    pub physics: Option<PhysicsWorld>, // Some = gravedad N-cuerpos en lugar de órbitas fijas
+    pub thread_pool: Option<PlanetThreadPool>, // Some = actualizar cuerpos en paralelo
 }
 
 impl SolarSystem {
-    /// Crea un sistema solar personalizado con 6 cuerpos
+    /// Crea el sistema solar por defecto (`assets/systems/default.toml`)
     pub fn new() -> Self {
-        let mut bodies = Vec::new();
-
-        // Sol (estrella central) - Amarillo brillante
-        bodies.push(CelestialBody::new(
-            "Solar heart",
-            2.5,                    // Radio grande
-            (1.0, 0.9, 0.3),        // Amarillo dorado
-            0.0,                    // Sin órbita (centro)
-            0.0,                    // Sin velocidad orbital
-            0.1,                    // Rotación lenta
-            true,                   // Emite luz
-            ShaderType::SolarHeart, // Shader solar_heart
-        ));
-
-        // Planeta 1: Ignis - Planeta volcánico de lava
-        bodies.push(CelestialBody::new(
-            "Lava",
-            0.4,
-            (0.7, 0.4, 0.3),        // Rojizo
-            5.0,                    // Órbita cercana
-            0.8,                    // Rápido
-            1.5,
-            false,
-            ShaderType::Lava,       // Shader de lava
-        ));
-
-        // Planeta 2: Terra - Planeta rocoso
-        bodies.push(CelestialBody::new(
-            "Rocky",
-            0.8,
-            (0.2, 0.5, 0.8),        // Azulado
-            8.0,
-            0.5,
-            1.0,
-            false,
-            ShaderType::Rocky,      // Shader rocoso
-        ));
-
-        // Planeta 3: Xenon - Planeta alien holográfico
-        bodies.push(CelestialBody::new(
-            "Alien",
-            0.6,
-            (0.5, 0.1, 0.6),        // Púrpura base
-            12.0,
-            0.35,
-            0.9,
-            false,
-            ShaderType::Alien,      // Shader alien holográfico
-        ));
-
-        // Planeta 4: Magnus - Gigante gaseoso
-        bodies.push(CelestialBody::new(
-            "Gas Giant",
-            1.5,
-            (0.8, 0.7, 0.5),        // Naranja/marrón
-            18.0,
-            0.2,
-            2.0,                    // Rotación rápida como Júpiter
-            false,
-            ShaderType::GasGiant,   // Shader de gigante gaseoso
-        ));
-
-        // Planeta 5: Glacius - Planeta de hielo
-        bodies.push(CelestialBody::new(
-            "Ice",
-            1.0,
-            (0.2, 0.3, 0.7),        // Azul oscuro
-            25.0,
-            0.12,
-            0.8,
-            false,
-            ShaderType::Ice,        // Shader de hielo
-        ));
+        parse_scene(DEFAULT_SCENE).expect("La escena por defecto es inválida")
+    }
 
+    /// Crea un sistema a partir de sus cuerpos, con la luz y cámara por defecto
+    pub fn from_bodies(name: &str, bodies: Vec<CelestialBody>) -> Self {
         // Generar puntos de órbita para cada cuerpo
-        let mut orbit_points = Vec::new();
-        for body in &bodies {
-            if body.orbit_radius > 0.0 {
-                orbit_points.push(generate_orbit_points(body.orbit_radius, 64));
-            } else {
-                orbit_points.push(Vec::new());
-            }
-        }
+        let orbit_points = bodies
+            .iter()
+            .map(|body| {
+                if body.orbit.radius > 0.0 && body.show_orbit {
+                    body.orbit.generate_orbit_path(64)
+                } else {
+                    Vec::new()
+                }
+            })
+            .collect();
 
-        Self { bodies, orbit_points }
+        let mut system = Self {
+            name: name.to_string(),
+            bodies,
+            orbit_points,
+            light: PointLight::default(),
+            camera_target: 0,
+            camera_distance: 35.0,
+            physics_settings: PhysicsSettings::default(),
+            physics: None,
+            thread_pool: None,
+        };
+        // Sistemas grandes se actualizan en paralelo automáticamente
+        if system.bodies.len() >= PlanetThreadPool::DEFAULT_MIN_BODIES {
+            system.set_parallel_update(true);
+        }
+        system.sync_light();
+        system
     }
 
     /// Actualiza todos los cuerpos
     pub fn update(&mut self, delta_time: f32) {
-        for body in &mut self.bodies {
-            body.update(delta_time);
+        match (&mut self.physics, &self.thread_pool) {
+            (Some(physics), pool) => {
+                physics.step(delta_time);
+                match pool {
+                    Some(pool) => pool.update_rotation(&mut self.bodies, delta_time),
+                    None => self.bodies.iter_mut().for_each(|b| b.update_rotation(delta_time)),
+                }
+            }
+            (None, Some(pool)) => pool.update(&mut self.bodies, delta_time),
+            (None, None) => {
+                for body in &mut self.bodies {
+                    body.update(delta_time);
+                }
+            }
+        }
+        self.sync_light();
+    }
+
+    /// Activa o desactiva la actualización paralela de los cuerpos
+    ///
+    /// El resultado es idéntico al serial; solo conviene con muchos cuerpos.
+    pub fn set_parallel_update(&mut self, enabled: bool) {
+        self.thread_pool = if enabled {
+            PlanetThreadPool::new(0).ok()
+        } else {
+            None
+        };
+    }
+
+    /// Activa o desactiva la simulación gravitacional
+    ///
+    /// Al activarla, cada cuerpo parte de su posición orbital actual con la
+    /// velocidad de su órbita (vis-viva) alrededor de su padre o de la estrella.
+    /// Al desactivarla, los cuerpos vuelven a sus órbitas cinemáticas.
+    pub fn set_physics(&mut self, enabled: bool) {
+        self.physics = if enabled {
+            Some(PhysicsWorld::new(self.physics_settings, self.initial_physics_states()))
+        } else {
+            None
+        };
+        self.sync_light();
+    }
+
+    pub fn physics_enabled(&self) -> bool {
+        self.physics.is_some()
+    }
+
+    /// Estados iniciales a partir de las órbitas cinemáticas actuales
+    fn initial_physics_states(&self) -> Vec<BodyState> {
+        let g = self.physics_settings.gravity;
+        let star = self.star_index();
+        let mut states: Vec<BodyState> = Vec::with_capacity(self.bodies.len());
+
+        for (i, body) in self.bodies.iter().enumerate() {
+            let position = self.get_body_position(i);
+            let mut velocity = Vec3::zero();
+
+            // Cuerpo central: el padre, o la estrella para los planetas
+            let primary = match body.parent {
+                Some(parent) if parent < i => Some(parent),
+                _ => star.filter(|&s| s < i),
+            };
+            if let (Some(p), true) = (primary, body.orbit.radius > 0.0) {
+                let r = (position - states[p].position).length().max(1e-3);
+                let mu = g * (states[p].mass + body.mass);
+                // v² = μ (2/r - 1/a); para órbitas circulares r = a
+                let speed = (mu * (2.0 / r - 1.0 / body.orbit.radius)).max(0.0).sqrt();
+                velocity = states[p].velocity + body.orbit.tangent() * speed;
+            }
+
+            states.push(BodyState { mass: body.mass, position, velocity });
+        }
+
+        // Momento total cero, para que el sistema no se desplace
+        let total_mass: f32 = states.iter().map(|s| s.mass).sum();
+        if total_mass > 0.0 {
+            let momentum = states
+                .iter()
+                .fold(Vec3::zero(), |acc, s| acc + s.velocity * s.mass);
+            let drift = momentum * (1.0 / total_mass);
+            for state in &mut states {
+                state.velocity = state.velocity - drift;
+            }
+        }
+        states
+    }
+
+    /// Copia el estado dinámico de todos los cuerpos
+    pub fn snapshot(&self) -> SystemSnapshot {
+        SystemSnapshot {
+            orbit_angles: self.bodies.iter().map(|b| b.orbit.current_angle).collect(),
+            rotation_angles: self.bodies.iter().map(|b| b.rotation_angle).collect(),
+            physics: self.physics.clone(),
+        }
+    }
+
+    /// Restaura un estado guardado con `snapshot`
+    ///
+    /// Falla si el snapshot es de un sistema con otra cantidad de cuerpos.
+    pub fn restore(&mut self, snapshot: &SystemSnapshot) -> Result<(), String> {
+        let count = self.bodies.len();
+        let physics_count = snapshot.physics.as_ref().map_or(count, |p| p.states.len());
+        if snapshot.orbit_angles.len() != count
+            || snapshot.rotation_angles.len() != count
+            || physics_count != count
+        {
+            return Err(format!(
+                "el snapshot no corresponde a este sistema ({} cuerpos)",
+                count
+            ));
+        }
+
+        for (i, body) in self.bodies.iter_mut().enumerate() {
+            body.orbit.current_angle = snapshot.orbit_angles[i];
+            body.rotation_angle = snapshot.rotation_angles[i];
+        }
+        self.physics = snapshot.physics.clone();
+        self.sync_light();
+        Ok(())
+    }
+
+    /// Índice de la estrella (primer cuerpo emisivo)
+    pub fn star_index(&self) -> Option<usize> {
+        self.bodies.iter().position(|b| b.is_emissive)
+    }
+
+    /// Coloca la luz en la posición actual de la estrella
+    pub(crate) fn sync_light(&mut self) {
+        if let Some(index) = self.star_index() {
+            self.light.position = self.get_body_position(index);
         }
     }
 
@@ -111,11 +208,54 @@ impl SolarSystem {
         self.bodies.get(index)
     }
 
-    /// Obtiene la posición de un cuerpo
+    /// Esferas (centro en el mundo, radio) de todos los cuerpos
+    pub fn body_spheres(&self) -> Vec<(Vec3, f32)> {
+        self.bodies
+            .iter()
+            .enumerate()
+            .map(|(i, body)| (self.get_body_position(i), body.radius))
+            .collect()
+    }
+
+    /// Obtiene la posición de un cuerpo en espacio mundo
     pub fn get_body_position(&self, index: usize) -> Vec3 {
-        self.bodies.get(index)
-            .map(|b| b.get_position())
-            .unwrap_or(Vec3::zero())
+        self.frame_matrix(index).transform_point(&Vec3::zero())
+    }
+
+    /// Marco de referencia de un cuerpo: traslaciones orbitales compuestas
+    /// desde la raíz de la jerarquía (sin rotaciones propias, para que las
+    /// lunas no giren con su planeta)
+    pub fn frame_matrix(&self, index: usize) -> Mat4 {
+        // Con física la posición mundo viene directamente de la simulación
+        if let Some(state) = self.physics.as_ref().and_then(|p| p.states.get(index)) {
+            let position = state.position;
+            return Mat4::translation(position.x, position.y, position.z);
+        }
+        match self.bodies.get(index) {
+            Some(body) => self.parent_frame(index).multiply(&body.get_orbit_matrix()),
+            None => Mat4::identity(),
+        }
+    }
+
+    /// Marco del padre de un cuerpo (identidad si orbita el origen)
+    ///
+    /// Las órbitas en `orbit_points` están en este espacio.
+    pub fn parent_frame(&self, index: usize) -> Mat4 {
+        match self.bodies.get(index).and_then(|b| b.parent) {
+            // El padre siempre está antes que el hijo, así no hay ciclos
+            Some(parent) if parent < index => self.frame_matrix(parent),
+            _ => Mat4::identity(),
+        }
+    }
+
+    /// Matriz de modelo de un cuerpo en espacio mundo
+    pub fn get_model_matrix(&self, index: usize) -> Mat4 {
+        match self.bodies.get(index) {
+            Some(body) => self
+                .frame_matrix(index)
+                .multiply(&Mat4::rotation_y(body.rotation_angle)),
+            None => Mat4::identity(),
+        }
     }
 
     /// Número de cuerpos
@@ -128,4 +268,52 @@ impl Default for SolarSystem {
     fn default() -> Self {
         Self::new()
     }
-}
\ No newline at end of file
+}
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BELT: &str = include_str!("../../assets/systems/asteroid_belt.toml");
+
+    /// El pool de hilos debe dar exactamente los mismos estados que el camino serial
+    fn assert_same_bodies(parallel: &SolarSystem, serial: &SolarSystem) {
+        for (i, (a, b)) in parallel.bodies.iter().zip(&serial.bodies).enumerate() {
+            assert_eq!(a.orbit.current_angle.to_bits(), b.orbit.current_angle.to_bits(), "órbita de {}", a.name);
+            assert_eq!(a.rotation_angle.to_bits(), b.rotation_angle.to_bits(), "rotación de {}", a.name);
+            let (p, q) = (parallel.get_body_position(i), serial.get_body_position(i));
+            assert_eq!((p.x, p.y, p.z), (q.x, q.y, q.z), "posición de {}", a.name);
+        }
+    }
+
+    fn belt_pair() -> (SolarSystem, SolarSystem) {
+        let parallel = parse_scene(BELT).unwrap();
+        let mut serial = parse_scene(BELT).unwrap();
+        assert!(parallel.thread_pool.is_some(), "el cinturón debería activar el pool");
+        serial.set_parallel_update(false);
+        (parallel, serial)
+    }
+
+    #[test]
+    fn parallel_update_matches_serial() {
+        let (mut parallel, mut serial) = belt_pair();
+        for step in 0..200 {
+            // Pasos variables, incluido uno hacia atrás
+            let dt = if step == 100 { -0.5 } else { 0.016 + step as f32 * 0.001 };
+            parallel.update(dt);
+            serial.update(dt);
+        }
+        assert_same_bodies(&parallel, &serial);
+    }
+
+    #[test]
+    fn parallel_rotation_matches_serial_with_physics() {
+        let (mut parallel, mut serial) = belt_pair();
+        parallel.set_physics(true);
+        serial.set_physics(true);
+        for _ in 0..20 {
+            parallel.update(0.05);
+            serial.update(0.05);
+        }
+        assert_same_bodies(&parallel, &serial);
+    }
+}
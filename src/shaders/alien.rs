@@ -59,7 +59,7 @@ pub fn shader_alien(fragment: &FragmentData, time: f32) -> (f32, f32, f32) {
     final_color = mix_v3(final_color, electric_aqua, spot_intensity * pulse_slow * 0.4);
 
     // Holographic rim glow (rainbow-like edge)
-    let view_dir = Vec3::new(-pos.x, -pos.y, -pos.z).normalize();
+    let view_dir = fragment.view_dir;
     let fresnel = (1.0 - (normal.x * view_dir.x + normal.y * view_dir.y + normal.z * view_dir.z).max(0.0))
         .powf(2.5);
 
@@ -41,6 +41,7 @@ pub fn fract_v3(v: Vec3) -> Vec3 {
 }
 
 /// Hash function (de GLSL shader para mejor calidad de ruido)
+#[allow(clippy::approx_constant)]
 pub fn hash_v3(p: Vec3) -> f32 {
     let mut p = fract_v3(Vec3::new(
         p.x * 0.3183099 + 0.1,
@@ -1,13 +1,13 @@
 use crate::math::Vec3;
 use super::common::*;
 use super::FragmentData;
+use super::lighting::LightSample;
 
 /// SHADER: GIGANTE GASEOSO (Portado de GLSL)
 /// Bandas horizontales tipo Júpiter con turbulencia
-pub fn shader_gas_giant(fragment: &FragmentData, time: f32) -> (f32, f32, f32) {
+pub fn shader_gas_giant(fragment: &FragmentData, time: f32, light: &LightSample) -> (f32, f32, f32) {
     let pos = fragment.position;
     let normal = fragment.normal.normalize();
-    let light_dir = Vec3::new(1.0, 1.0, 2.0).normalize();
 
     // Create horizontal bands based on Y position
     let mut bands = pos.y * 5.0 + time * 0.2;
@@ -46,7 +46,7 @@ pub fn shader_gas_giant(fragment: &FragmentData, time: f32) -> (f32, f32, f32) {
     };
 
     // Add atmospheric glow
-    let view_dir = Vec3::new(0.0, 0.0, 1.0);
+    let view_dir = fragment.view_dir;
     let atmosphere = (1.0 - (normal.x * view_dir.x + normal.y * view_dir.y + normal.z * view_dir.z).abs())
         .powf(2.0);
     
@@ -57,12 +57,12 @@ pub fn shader_gas_giant(fragment: &FragmentData, time: f32) -> (f32, f32, f32) {
     );
 
     // Lighting
-    let diffuse = (normal.x * light_dir.x + normal.y * light_dir.y + normal.z * light_dir.z).max(0.0);
+    let diffuse = light.radiance(normal);
     let ambient = 0.4;
 
-    final_color.x *= ambient + diffuse * 0.6;
-    final_color.y *= ambient + diffuse * 0.6;
-    final_color.z *= ambient + diffuse * 0.6;
+    final_color.x *= ambient + diffuse.x * 0.6;
+    final_color.y *= ambient + diffuse.y * 0.6;
+    final_color.z *= ambient + diffuse.z * 0.6;
 
     clamp_color(final_color.x, final_color.y, final_color.z)
 }
\ No newline at end of file
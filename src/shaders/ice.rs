@@ -1,10 +1,11 @@
 use crate::math::Vec3;
 use super::common::*;
 use super::FragmentData;
+use super::lighting::LightSample;
 
 /// SHADER: PLANETA DE HIELO (Extra 2)
 /// Hielo con grietas, nieve, cristales y auroras boreales
-pub fn shader_ice(fragment: &FragmentData, time: f32) -> (f32, f32, f32) {
+pub fn shader_ice(fragment: &FragmentData, time: f32, light: &LightSample) -> (f32, f32, f32) {
     let pos = fragment.position;
     let normal = fragment.normal.normalize();
 
@@ -58,7 +59,7 @@ pub fn shader_ice(fragment: &FragmentData, time: f32) -> (f32, f32, f32) {
     };
 
     // CAPA 6: Atmósfera helada (rim glow)
-    let view_dir = Vec3::new(-pos.x, -pos.y, -pos.z).normalize();
+    let view_dir = fragment.view_dir;
     let rim = (1.0 - (normal.x * view_dir.x + normal.y * view_dir.y + normal.z * view_dir.z).max(0.0))
         .powf(2.0) * 0.3;
 
@@ -75,19 +76,23 @@ pub fn shader_ice(fragment: &FragmentData, time: f32) -> (f32, f32, f32) {
     final_color.z = final_color.z * (1.0 - snow_coverage) + fresh_snow.z * snow_coverage;
 
     // Iluminación especular fuerte (hielo muy reflectivo)
-    let light_dir = Vec3::new(1.0, 1.0, 2.0).normalize();
-    let diffuse = (normal.x * light_dir.x + normal.y * light_dir.y + normal.z * light_dir.z).max(0.0);
-    let specular = diffuse.powf(4.0) * 0.5;
+    let diffuse = light.diffuse(normal);
+    let half_vec = (light.direction + view_dir).normalize();
+    let specular = if diffuse > 0.0 {
+        normal.dot(&half_vec).max(0.0).powf(24.0) * 0.5
+    } else {
+        0.0
+    };
     let ambient = 0.5; // Base ambient lighting
     
     // Apply diffuse lighting to base color
-    final_color.x *= (ambient + diffuse * 0.8);
-    final_color.y *= (ambient + diffuse * 0.8);
-    final_color.z *= (ambient + diffuse * 0.8);
+    final_color.x *= ambient + diffuse * light.color.x * 0.8;
+    final_color.y *= ambient + diffuse * light.color.y * 0.8;
+    final_color.z *= ambient + diffuse * light.color.z * 0.8;
     
-    final_color.x += specular;
-    final_color.y += specular;
-    final_color.z += specular;
+    final_color.x += specular * light.color.x;
+    final_color.y += specular * light.color.y;
+    final_color.z += specular * light.color.z;
 
     // Añadir cristales brillantes
     final_color.x += sparkle * 0.4;
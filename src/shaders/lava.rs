@@ -1,13 +1,13 @@
 use crate::math::Vec3;
 use super::common::*;
 use super::FragmentData;
+use super::lighting::LightSample;
 
 /// SHADER: PLANETA DE LAVA (Extra 1)
 /// Naranja y rojo con sombras negras
-pub fn shader_lava(fragment: &FragmentData, time: f32) -> (f32, f32, f32) {
+pub fn shader_lava(fragment: &FragmentData, time: f32, light: &LightSample) -> (f32, f32, f32) {
     let pos = fragment.position;
     let normal = fragment.normal.normalize();
-    let light_dir = Vec3::new(1.0, 1.0, 2.0).normalize();
 
     // Lava flow pattern with animation
     let flow1 = fbm(pos.x * 2.0 + time * 0.3, pos.z * 2.0 + time * 0.25, 3);
@@ -53,14 +53,18 @@ pub fn shader_lava(fragment: &FragmentData, time: f32) -> (f32, f32, f32) {
     let heat_boost = pulse * is_lava * 0.15;
 
     // Lighting for shadows on dark crust
-    let diffuse = (normal.x * light_dir.x + normal.y * light_dir.y + normal.z * light_dir.z).max(0.0);
-    let shadow_factor = 0.1 + diffuse * 0.3;
+    let diffuse = light.radiance(normal);
+    let shadow_factor = Vec3::new(
+        0.1 + diffuse.x * 0.3,
+        0.1 + diffuse.y * 0.3,
+        0.1 + diffuse.z * 0.3,
+    );
 
     // Apply shadows only to crust (not lava - it emits light)
     let crust_with_shadow = Vec3::new(
-        color_with_cracks.x * (is_lava + (1.0 - is_lava) * shadow_factor),
-        color_with_cracks.y * (is_lava + (1.0 - is_lava) * shadow_factor),
-        color_with_cracks.z * (is_lava + (1.0 - is_lava) * shadow_factor),
+        color_with_cracks.x * (is_lava + (1.0 - is_lava) * shadow_factor.x),
+        color_with_cracks.y * (is_lava + (1.0 - is_lava) * shadow_factor.y),
+        color_with_cracks.z * (is_lava + (1.0 - is_lava) * shadow_factor.z),
     );
 
     // Add emission glow
@@ -0,0 +1,69 @@
+use crate::math::Vec3;
+
+/// Luz puntual emitida por la estrella del sistema
+#[derive(Debug, Clone, Copy)]
+pub struct PointLight {
+    pub position: Vec3,
+    pub color: (f32, f32, f32), // RGB normalizado 0.0-1.0
+    pub intensity: f32,
+    pub falloff: f32,           // Atenuación cuadrática con la distancia
+}
+
+impl PointLight {
+    pub fn new(position: Vec3, color: (f32, f32, f32), intensity: f32, falloff: f32) -> Self {
+        Self { position, color, intensity, falloff }
+    }
+
+    /// Atenuación en función de la distancia: intensity / (1 + falloff * d²)
+    pub fn attenuation(&self, distance: f32) -> f32 {
+        self.intensity / (1.0 + self.falloff * distance * distance)
+    }
+
+    /// Evalúa la luz que llega a un punto del espacio mundo
+    pub fn illuminate(&self, point: Vec3) -> LightSample {
+        let to_light = self.position - point;
+        let distance = to_light.length();
+        let attenuation = self.attenuation(distance);
+        LightSample {
+            direction: to_light.normalize(),
+            color: Vec3::new(
+                self.color.0 * attenuation,
+                self.color.1 * attenuation,
+                self.color.2 * attenuation,
+            ),
+        }
+    }
+}
+
+impl Default for PointLight {
+    fn default() -> Self {
+        Self::new(Vec3::zero(), (1.0, 0.95, 0.85), 1.1, 0.0008)
+    }
+}
+
+/// Luz que llega a un fragmento: dirección hacia la estrella y color atenuado
+#[derive(Debug, Clone, Copy)]
+pub struct LightSample {
+    pub direction: Vec3, // Normalizada, apunta hacia la luz
+    pub color: Vec3,     // Color * intensidad * atenuación
+}
+
+impl LightSample {
+    /// Sin luz externa (cuerpos emisivos)
+    pub fn none() -> Self {
+        Self {
+            direction: Vec3::zero(),
+            color: Vec3::zero(),
+        }
+    }
+
+    /// Término de Lambert (n · l), sin color
+    pub fn diffuse(&self, normal: Vec3) -> f32 {
+        normal.dot(&self.direction).max(0.0)
+    }
+
+    /// Radiancia difusa por canal (color de la luz * n · l)
+    pub fn radiance(&self, normal: Vec3) -> Vec3 {
+        self.color * self.diffuse(normal)
+    }
+}
@@ -1,4 +1,5 @@
 pub mod common;
+pub mod lighting;
 pub mod solar_heart;
 pub mod rocky;
 pub mod gas_giant;
@@ -7,6 +8,7 @@ pub mod lava;
 pub mod alien;
 
 pub use common::*;
+pub use lighting::{PointLight, LightSample};
 pub use solar_heart::shader_solar_heart;
 pub use rocky::shader_rocky;
 pub use gas_giant::shader_gas_giant;
@@ -14,7 +16,7 @@ pub use ice::shader_ice;
 pub use lava::shader_lava;
 pub use alien::shader_alien;
 
-use crate::math::Vec3;
+use crate::math::{Vec3, Mat4};
 
 /// Tipos de shader disponibles
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -27,12 +29,44 @@ pub enum ShaderType {
     Alien,      // Planeta alien holográfico
 }
 
+impl ShaderType {
+    pub const ALL: [ShaderType; 6] = [
+        ShaderType::SolarHeart,
+        ShaderType::Rocky,
+        ShaderType::GasGiant,
+        ShaderType::Lava,
+        ShaderType::Ice,
+        ShaderType::Alien,
+    ];
+
+    /// Nombre usado en los archivos de escena
+    pub fn name(&self) -> &'static str {
+        match self {
+            ShaderType::SolarHeart => "solar_heart",
+            ShaderType::Rocky => "rocky",
+            ShaderType::GasGiant => "gas_giant",
+            ShaderType::Lava => "lava",
+            ShaderType::Ice => "ice",
+            ShaderType::Alien => "alien",
+        }
+    }
+
+    /// Interpreta un nombre de shader (sin distinguir mayúsculas)
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|shader| shader.name().eq_ignore_ascii_case(name))
+    }
+}
+
 /// Datos del fragmento para el shader
 #[derive(Debug, Clone, Copy)]
 pub struct FragmentData {
-    pub position: Vec3,      // Posición en espacio local
-    pub normal: Vec3,        // Normal del fragmento
+    pub position: Vec3,      // Posición en espacio local (esfera unitaria)
+    pub normal: Vec3,        // Normal del fragmento (espacio mundo)
     pub world_pos: Vec3,     // Posición en espacio mundo
+    pub view_dir: Vec3,      // Dirección normalizada del fragmento hacia la cámara
+    pub model: Mat4,         // Matriz de modelo del cuerpo
 }
 
 /// Aplica el shader correspondiente
@@ -40,14 +74,14 @@ pub fn apply_shader(
     shader_type: ShaderType,
     fragment: &FragmentData,
     time: f32,
-    _light_dir: &Vec3,
+    light: &LightSample,
 ) -> (f32, f32, f32) {
     match shader_type {
         ShaderType::SolarHeart => shader_solar_heart(fragment, time),
-        ShaderType::Rocky => shader_rocky(fragment, time),
-        ShaderType::GasGiant => shader_gas_giant(fragment, time),
-        ShaderType::Lava => shader_lava(fragment, time),
-        ShaderType::Ice => shader_ice(fragment, time),
+        ShaderType::Rocky => shader_rocky(fragment, time, light),
+        ShaderType::GasGiant => shader_gas_giant(fragment, time, light),
+        ShaderType::Lava => shader_lava(fragment, time, light),
+        ShaderType::Ice => shader_ice(fragment, time, light),
         ShaderType::Alien => shader_alien(fragment, time),
     }
 }
\ No newline at end of file
@@ -1,13 +1,13 @@
 use crate::math::Vec3;
 use super::common::*;
 use super::FragmentData;
+use super::lighting::LightSample;
 
 /// SHADER: PLANETA ROCOSO (Portado de GLSL)
 /// Paleta de azules claros con terreno y cráteres
-pub fn shader_rocky(fragment: &FragmentData, _time: f32) -> (f32, f32, f32) {
+pub fn shader_rocky(fragment: &FragmentData, _time: f32, light: &LightSample) -> (f32, f32, f32) {
     let pos = fragment.position;
     let normal = fragment.normal.normalize();
-    let light_dir = Vec3::new(1.0, 1.0, 2.0).normalize();
 
     // Calculate noise-based terrain
     let terrain = fbm_3d(Vec3::new(pos.x * 3.0, pos.y * 3.0, pos.z * 3.0), 5);
@@ -28,13 +28,13 @@ pub fn shader_rocky(fragment: &FragmentData, _time: f32) -> (f32, f32, f32) {
     base_color.z += rock_detail * 0.1;
 
     // Lighting (diffuse + ambient) - increased for visibility
-    let diffuse = (normal.x * light_dir.x + normal.y * light_dir.y + normal.z * light_dir.z).max(0.0);
+    let diffuse = light.radiance(normal);
     let ambient = 0.5; // Increased ambient light
 
     let final_color = Vec3::new(
-        base_color.x * (ambient + diffuse * 1.0), // Increased diffuse contribution
-        base_color.y * (ambient + diffuse * 1.0),
-        base_color.z * (ambient + diffuse * 1.0),
+        base_color.x * (ambient + diffuse.x), // Full diffuse contribution
+        base_color.y * (ambient + diffuse.y),
+        base_color.z * (ambient + diffuse.z),
     );
 
     clamp_color(final_color.x, final_color.y, final_color.z)
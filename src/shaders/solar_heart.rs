@@ -120,8 +120,7 @@ pub fn shader_solar_heart(fragment: &FragmentData, time: f32) -> (f32, f32, f32)
         pos.z * 15.0 - time * 0.5,
     ));
 
-    let normal_view_dot = (normal.x * (-pos.x) + normal.y * (-pos.y) + normal.z * (-pos.z))
-        / ((pos.x * pos.x + pos.y * pos.y + pos.z * pos.z).sqrt() + 0.001);
+    let normal_view_dot = normal.dot(&fragment.view_dir);
     let is_at_edge = 1.0 - normal_view_dot.abs();
 
     let flare_threshold = 0.55;
@@ -186,7 +185,7 @@ pub fn shader_solar_heart(fragment: &FragmentData, time: f32) -> (f32, f32, f32)
     }
 
     // === LIMB EFFECTS ===
-    let view_dir = Vec3::new(-pos.x, -pos.y, -pos.z).normalize();
+    let view_dir = fragment.view_dir;
     let edge_factor = 1.0 - (normal.x * view_dir.x + normal.y * view_dir.y + normal.z * view_dir.z).max(0.0);
     let edge_glow = edge_factor.powf(1.8) * 0.85;
 
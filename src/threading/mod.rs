@@ -1,3 +1,3 @@
 pub mod planet_thread;
 
-pub use planet_thread::{PlanetWorker, PlanetThreadPool, PlanetState, PlanetCommand};
\ No newline at end of file
+pub use planet_thread::PlanetThreadPool;
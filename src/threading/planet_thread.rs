@@ -1,170 +1,58 @@
-use std::sync::mpsc;
-use std::thread::{self, JoinHandle};
-use crate::math::Vec3;
-
-/// Mensaje para comunicar estado de un planeta
-#[derive(Debug, Clone)]
-pub struct PlanetState {
-    pub index: usize,
-    pub position: Vec3,
-    pub rotation_angle: f32,
-    pub orbit_angle: f32,
-}
-
-/// Comando que puede recibir un hilo de planeta
-#[derive(Debug, Clone)]
-pub enum PlanetCommand {
-    Update(f32),  // delta_time
-    Stop,
-}
-
-/// Worker que actualiza un planeta en su propio hilo
-pub struct PlanetWorker {
-    pub index: usize,
-    pub handle: Option<JoinHandle<()>>,
-    pub command_sender: mpsc::Sender<PlanetCommand>,
-}
-
-impl PlanetWorker {
-    /// Crea un nuevo worker para un planeta
-    pub fn new(
-        index: usize,
-        orbit_radius: f32,
-        orbit_speed: f32,
-        rotation_speed: f32,
-        initial_orbit_angle: f32,
-        state_sender: mpsc::Sender<PlanetState>,
-    ) -> Self {
-        let (command_sender, command_receiver) = mpsc::channel::<PlanetCommand>();
-
-        let handle = thread::spawn(move || {
-            let mut orbit_angle = initial_orbit_angle;
-            let mut rotation_angle = 0.0f32;
-
-            loop {
-                match command_receiver.recv() {
-                    Ok(PlanetCommand::Update(delta_time)) => {
-                        // Actualizar ángulos
-                        orbit_angle += orbit_speed * delta_time;
-                        rotation_angle += rotation_speed * delta_time;
-
-                        // Normalizar ángulos
-                        if orbit_angle > std::f32::consts::TAU {
-                            orbit_angle -= std::f32::consts::TAU;
-                        }
-                        if rotation_angle > std::f32::consts::TAU {
-                            rotation_angle -= std::f32::consts::TAU;
-                        }
-
-                        // Calcular posición
-                        let position = Vec3::new(
-                            orbit_radius * orbit_angle.cos(),
-                            0.0,
-                            orbit_radius * orbit_angle.sin(),
-                        );
-
-                        // Enviar estado actualizado
-                        let state = PlanetState {
-                            index,
-                            position,
-                            rotation_angle,
-                            orbit_angle,
-                        };
-
-                        if state_sender.send(state).is_err() {
-                            break; // Canal cerrado, terminar
-                        }
-                    }
-                    Ok(PlanetCommand::Stop) | Err(_) => {
-                        break;
-                    }
-                }
-            }
-        });
-
-        Self {
-            index,
-            handle: Some(handle),
-            command_sender,
-        }
-    }
-
-    /// Envía comando de actualización al hilo
-    pub fn send_update(&self, delta_time: f32) {
-        let _ = self.command_sender.send(PlanetCommand::Update(delta_time));
-    }
-
-    /// Detiene el worker
-    pub fn stop(&mut self) {
-        let _ = self.command_sender.send(PlanetCommand::Stop);
-        if let Some(handle) = self.handle.take() {
-            let _ = handle.join();
-        }
-    }
-}
-
-impl Drop for PlanetWorker {
-    fn drop(&mut self) {
-        self.stop();
-    }
-}
-
-/// Administrador de todos los workers de planetas
+use rayon::prelude::*;
+use rayon::{ThreadPool, ThreadPoolBuildError, ThreadPoolBuilder};
+
+use crate::scene::CelestialBody;
+
+/// Pool de hilos para actualizar los cuerpos celestes en paralelo
+///
+/// Cada cuerpo se actualiza con `CelestialBody::update`, la misma matemática
+/// orbital del camino serial, y `update` no vuelve hasta que todos terminaron:
+/// los estados siempre corresponden al frame actual. Con pocos cuerpos el
+/// costo de repartir el trabajo supera la ganancia, por eso por debajo de
+/// `min_bodies` se actualiza en serie.
 pub struct PlanetThreadPool {
-    workers: Vec<PlanetWorker>,
-    state_receiver: mpsc::Receiver<PlanetState>,
-    pub latest_states: Vec<Option<PlanetState>>,
+    pool: ThreadPool,
+    pub min_bodies: usize, // Cuerpos mínimos para repartir entre hilos
 }
 
 impl PlanetThreadPool {
-    /// Crea un pool vacío
-    pub fn new(planet_count: usize) -> (Self, mpsc::Sender<PlanetState>) {
-        let (state_sender, state_receiver) = mpsc::channel();
-        
-        let pool = Self {
-            workers: Vec::new(),
-            state_receiver,
-            latest_states: vec![None; planet_count],
-        };
-        
-        (pool, state_sender)
-    }
+    /// Tamaño de sistema a partir del cual conviene actualizar en paralelo
+    pub const DEFAULT_MIN_BODIES: usize = 64;
 
-    /// Agrega un worker al pool
-    pub fn add_worker(&mut self, worker: PlanetWorker) {
-        self.workers.push(worker);
+    /// Crea un pool con `threads` hilos (0 = uno por núcleo)
+    pub fn new(threads: usize) -> Result<Self, ThreadPoolBuildError> {
+        let pool = ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .thread_name(|i| format!("planet-update-{}", i))
+            .build()?;
+        Ok(Self {
+            pool,
+            min_bodies: Self::DEFAULT_MIN_BODIES,
+        })
     }
 
-    /// Envía comando de actualización a todos los workers
-    pub fn update_all(&mut self, delta_time: f32) {
-        for worker in &self.workers {
-            worker.send_update(delta_time);
-        }
+    /// Número de hilos del pool
+    pub fn threads(&self) -> usize {
+        self.pool.current_num_threads()
+    }
 
-        // Recolectar estados actualizados (non-blocking)
-        while let Ok(state) = self.state_receiver.try_recv() {
-            let index = state.index;
-            if index < self.latest_states.len() {
-                self.latest_states[index] = Some(state);
-            }
-        }
+    /// Actualiza órbita y rotación de todos los cuerpos y espera el resultado
+    pub fn update(&self, bodies: &mut [CelestialBody], delta_time: f32) {
+        self.for_each(bodies, |body| body.update(delta_time));
     }
 
-    /// Obtiene el estado más reciente de un planeta
-    pub fn get_state(&self, index: usize) -> Option<&PlanetState> {
-        self.latest_states.get(index).and_then(|s| s.as_ref())
+    /// Actualiza solo la rotación (cuando las posiciones vienen de la física)
+    pub fn update_rotation(&self, bodies: &mut [CelestialBody], delta_time: f32) {
+        self.for_each(bodies, |body| body.update_rotation(delta_time));
     }
 
-    /// Detiene todos los workers
-    pub fn stop_all(&mut self) {
-        for worker in &mut self.workers {
-            worker.stop();
+    fn for_each(&self, bodies: &mut [CelestialBody], update: impl Fn(&mut CelestialBody) + Sync + Send) {
+        if bodies.len() < self.min_bodies {
+            bodies.iter_mut().for_each(update);
+        } else {
+            // install + for_each bloquean hasta que todos los hilos terminan
+            self.pool
+                .install(|| bodies.par_iter_mut().with_min_len(16).for_each(update));
         }
     }
 }
-
-impl Drop for PlanetThreadPool {
-    fn drop(&mut self) {
-        self.stop_all();
-    }
-}
\ No newline at end of file